@@ -0,0 +1,221 @@
+use crate::{convert, parse_conversion, stream_error, Conversion, Converted, Settings};
+use colored::*;
+use std::io::{BufRead, BufReader, Write};
+
+// splits a csv row into its fields, which can be quoted to hold
+// commas, with "" standing for a quote within quotes
+fn split_row(row: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = row.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+        .into_iter()
+        .map(|field| field.trim().to_string())
+        .collect()
+}
+
+// a field as it is written in a csv row, quoted when it has to be
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// a csv header, whose first column is time
+fn is_header(line: &str) -> bool {
+    line.contains(',')
+        && split_row(line)
+            .first()
+            .is_some_and(|column| column.eq_ignore_ascii_case("time"))
+}
+
+// time,origin,destinations,date where the destinations are
+// separated by spaces and the date can be left out
+fn parse_row(row: &str) -> Conversion {
+    let columns = split_row(row);
+    let column = |i: usize| columns.get(i).filter(|column| !column.is_empty()).cloned();
+
+    Conversion {
+        time: columns[0].clone(),
+        origin: column(1),
+        destinations: column(2)
+            .map(|destinations| destinations.split_whitespace().map(String::from).collect())
+            .unwrap_or_default(),
+        adjustments: vec![],
+        date: column(3),
+    }
+}
+
+// the result of one line, which is the same line with a column
+// added for each destination when it is a csv row
+fn convert_line(line: &str, csv: bool, settings: &Settings) -> Result<String, String> {
+    if csv {
+        let mut conversion = parse_row(line);
+        conversion.destinations = settings.config.expand_teams(conversion.destinations);
+
+        let (_, converted) = convert(conversion, settings)?;
+        let times: Vec<String> = converted.iter().map(|c| csv_field(&c.time)).collect();
+
+        return Ok(format!("{line},{}", times.join(",")));
+    }
+
+    let args = settings
+        .config
+        .expand_teams(line.split_whitespace().map(String::from).collect());
+    let (given, converted) =
+        parse_conversion(args, settings).and_then(|conversion| convert(conversion, settings))?;
+
+    let destinations: Vec<String> = converted
+        .into_iter()
+        .map(|converted: Converted| {
+            let destination = converted.destination.unwrap_or("local time".to_string());
+            format!("{} {destination}", converted.time)
+        })
+        .collect();
+
+    Ok(format!("{given} is {}", destinations.join(", ")))
+}
+
+// what is printed for line `number` of the input: blank lines and
+// comments as they are, and otherwise the result or the error
+fn output_line(number: usize, line: &str, csv: bool, settings: &Settings) -> String {
+    let trimmed = line.trim();
+
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return line.to_string();
+    }
+
+    if number == 1 && csv && is_header(trimmed) {
+        return format!("{trimmed},converted");
+    }
+
+    match convert_line(trimmed, csv, settings) {
+        Ok(result) => result,
+        Err(error) => format!(
+            "[{}] line {number}: {}",
+            "ERROR".red(),
+            error.lines().next().unwrap_or_default()
+        ),
+    }
+}
+
+/*
+ * meetings.txt        - converts each line of the file, or stdin if
+ *                       there is none, printing one line for each,
+ *                       including blank lines and errors, so that the
+ *                       output lines up with the input
+ * meetings.csv --csv  - every line is a csv row, which it also is
+ *                       when the first line is a header: time,...
+ */
+pub fn batch(mut args: Vec<String>, settings: &Settings) {
+    let mut csv = args.iter().any(|arg| arg == "--csv");
+    args.retain(|arg| arg != "--csv");
+
+    let input: Box<dyn BufRead> = match args.first() {
+        Some(path) if path != "-" => match std::fs::File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(error) => {
+                return println!("\n[{}] cannot read {path}: {error}\n", "ERROR".red());
+            }
+        },
+        _ => Box::new(BufReader::new(std::io::stdin())),
+    };
+
+    let mut stdout = std::io::stdout().lock();
+
+    for (i, line) in input.lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => {
+                return println!("[{}] line {}: {error}", "ERROR".red(), i + 1);
+            }
+        };
+
+        if i == 0 && is_header(line.trim()) {
+            csv = true;
+        }
+
+        if let Err(error) = writeln!(stdout, "{}", output_line(i + 1, &line, csv, settings)) {
+            return stream_error(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::settings;
+
+    fn output(lines: &[&str], csv: bool) -> Vec<String> {
+        let settings = settings();
+
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| output_line(i + 1, line, csv, &settings))
+            .collect()
+    }
+
+    #[test]
+    fn bad_rows_do_not_stop_the_rest() {
+        let output = output(
+            &[
+                "1pm,et,bst,2025-06-04",
+                "2:30am,et,bst,2025-03-09",
+                "3pm,et,bst,2025-06-04",
+            ],
+            true,
+        );
+
+        assert_eq!(output[0], "1pm,et,bst,2025-06-04,6:00pm");
+        assert!(output[1].contains("line 2: 02:30 does not exist in et"));
+        assert_eq!(output[2], "3pm,et,bst,2025-06-04,8:00pm");
+    }
+
+    #[test]
+    fn dates_with_commas_are_not_csv() {
+        assert_eq!(
+            output(&["1pm et bst february 1st, 2025"], false),
+            vec!["1pm et february 1st, 2025 is 7:00pm bst"]
+        );
+    }
+
+    #[test]
+    fn quoted_csv_fields() {
+        assert_eq!(
+            output(
+                &[
+                    "time,origin,destination,date",
+                    r#"1pm,et,"bst ist","february 1st, 2025""#,
+                ],
+                true
+            ),
+            vec![
+                "time,origin,destination,date,converted",
+                r#"1pm,et,"bst ist","february 1st, 2025",7:00pm,11:30pm"#,
+            ]
+        );
+        assert_eq!(
+            split_row(r#"a,"b ""c"", d",e"#),
+            vec!["a", r#"b "c", d"#, "e"]
+        );
+        assert_eq!(csv_field("9.00, pm"), r#""9.00, pm""#);
+    }
+}
@@ -0,0 +1,661 @@
+use colored::*;
+
+// the command line, from which --help, tz help, the man page and
+// the shell completions are all written
+
+// a flag and the value it takes, if any
+pub struct Flag {
+    pub name: &'static str,
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+// a subcommand, or a mode such as --batch. The conversion itself
+// has no name.
+pub struct Command {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub summary: &'static str,
+    pub description: &'static str, // paragraphs, tab indented lines are examples
+    pub flags: &'static [Flag],
+    pub examples: &'static [&'static str],
+}
+
+pub static GLOBAL_FLAGS: &[Flag] = &[
+    Flag {
+        name: "--lang",
+        value: Some("fr|de|es|pt|it|nl"),
+        help: "the language of dates, otherwise from $LC_ALL, $LC_TIME or $LANG",
+    },
+    Flag {
+        name: "--date-order",
+        value: Some("dmy|mdy"),
+        help: "whether 02/03 is the 2nd of March or the 3rd of February",
+    },
+    Flag {
+        name: "--config",
+        value: Some("file"),
+        help: "the config file to read instead of the usual one",
+    },
+    Flag {
+        name: "--color",
+        value: Some("auto|always|never"),
+        help: "whether to colour the output, by default only on a terminal",
+    },
+    Flag {
+        name: "--quiet",
+        value: None,
+        help: "only the converted times, one per line, also -q",
+    },
+    Flag {
+        name: "--no-warnings",
+        value: None,
+        help: "no warning when falling back to the local timezone",
+    },
+    Flag {
+        name: "--help",
+        value: None,
+        help: "this help, or the help of the command it follows",
+    },
+];
+
+pub static COMMANDS: &[Command] = &[
+    Command {
+        name: "",
+        usage: "tz time origin_timezone destination_timezone... [date]",
+        summary: "convert a time from one timezone to others",
+        description: "time should be in one of the following formats:
+
+\tSimpleAmPm    - eg. 1am, 10pm, etc.
+\tFullAmPm      - eg. 12:24am, 6:30pm, etc.
+\tMilitaryColon - eg. 07:00, 13:52, etc.
+\tMilitary      - eg. 0900, 1634, etc.
+
+or as a range of two times, eg. 9am-5pm, 9-5pm, 13:00-14:30,
+9am to 5pm, which is converted as a span.
+
+The time can be shifted by a duration, eg. 2pm et + 45m bst,
+and can be now or in a duration, eg. now + 90m ist, or
+in 6 hours aet, in which case every timezone is a destination.
+Durations are written as 90m, 1h30m, 2d, 3 hours, or PT1H30M.
+
+The origin and destination timezones can be either a city,
+such as Europe/London, or a timezone abbreviation, such
+as gmt, or a person or team from the config file, such as
+@alice. Several destinations can be given at once. The
+accepted timezone names are listed by tz zones.
+
+The date can be written in most of the usual ways:
+
+\t20 feb, feb 20, 20th feb 2024, the 1st of march
+\t20/02/2024, 20.02.2024, 2/20, 2024-03-20
+\ttoday, tomorrow, yesterday, day after tomorrow
+\tmonday, next friday, last tue
+\tnext week, in 3 days, +2, -1
+
+Month names, weekdays, and words such as tomorrow are also
+understood in French, German, Spanish, Portuguese, Italian,
+and Dutch, chosen by $LANG or with --lang.
+
+Numeric dates such as 02/03 are read day first, or month
+first if your locale is en_US, unless --date-order is given.
+
+Only the time and the origin are needed. Without any
+destinations, the time is converted to the destinations in
+the config file, or else to the local timezone, which is
+local in the config file, or else $TZ, or else the timezone
+that /etc/localtime links to, or else the current UTC offset.
+Without a date, the time is today.
+
+Conversions to a timezone with working hours configured say
+whether the time is within them.",
+        flags: &[],
+        examples: &[
+            "tz 1pm et bst tomorrow",
+            "tz 9am-5pm et ist",
+            "tz 2pm et + 45m bst ist",
+            "tz in 6 hours aet",
+            "tz -q now ist",
+        ],
+    },
+    Command {
+        name: "offset",
+        usage: "tz offset timezone timezone",
+        summary: "how far apart two timezones are, now and over the coming year",
+        description: "Gives how far ahead of the first timezone the clocks in the
+second are now, and when that changes over the coming year as
+either changes for daylight saving.",
+        flags: &[],
+        examples: &["tz offset pt ist"],
+    },
+    Command {
+        name: "transitions",
+        usage: "tz transitions timezone [--year year]",
+        summary: "when a timezone changes its UTC offset",
+        description: "Lists the changes of UTC offset of a timezone, such as the
+clocks going forward for daylight saving, over the past and
+coming year, or in a given year.",
+        flags: &[Flag {
+            name: "--year",
+            value: Some("year"),
+            help: "the year to list the changes in",
+        }],
+        examples: &["tz transitions Europe/London --year 2025"],
+    },
+    Command {
+        name: "plan",
+        usage: "tz plan timezone... [--hours 9-17] [--date date]",
+        summary: "find a meeting time when everyone is in working hours",
+        description: "Ranks the times of a day by how many of the timezones are
+within their working hours, which are those in the config
+file unless --hours is given. The day is today in the first
+timezone unless --date is given.",
+        flags: &[
+            Flag {
+                name: "--hours",
+                value: Some("hours"),
+                help: "the working hours of everyone, eg. 9-17 or 9am-5pm",
+            },
+            Flag {
+                name: "--date",
+                value: Some("date"),
+                help: "the day to plan, in the first timezone",
+            },
+        ],
+        examples: &["tz plan pt et bst ist --hours 9-17 --date next tue"],
+    },
+    Command {
+        name: "grid",
+        usage: "tz grid timezone... [date]",
+        summary: "the hours of a day side by side across timezones",
+        description: "Shows each hour of the day in the first timezone next to the
+same instant in the others, with working hours and the night
+highlighted, and the current hour marked.",
+        flags: &[],
+        examples: &["tz grid pt et bst ist tomorrow"],
+    },
+    Command {
+        name: "diff",
+        usage: "tz diff time timezone [date] -- time timezone [date]",
+        summary: "the time between two times",
+        description: "Gives the time elapsed between two times, possibly in
+different timezones, and how much of it is within working
+hours in each. The date of the second time is relative to the
+first, and either time can be now.",
+        flags: &[],
+        examples: &["tz diff 9am pt friday -- 10am ist monday"],
+    },
+    Command {
+        name: "people",
+        usage: "tz people",
+        summary: "the people and teams in the config file",
+        description: "Lists the people in the config file with their timezone and
+the time where they are now, and the teams with their people.",
+        flags: &[],
+        examples: &["tz people"],
+    },
+    Command {
+        name: "zones",
+        usage: "tz zones [query] [--abbr|--iana] [--offset offset]",
+        summary: "search the accepted timezone names",
+        description: "Lists the accepted timezone names containing the query, with
+the IANA timezone each stands for, its current UTC offset,
+and its current abbreviation.",
+        flags: &[
+            Flag {
+                name: "--abbr",
+                value: None,
+                help: "only abbreviations, such as ist",
+            },
+            Flag {
+                name: "--iana",
+                value: None,
+                help: "only IANA names, such as Asia/Kolkata",
+            },
+            Flag {
+                name: "--offset",
+                value: Some("offset"),
+                help: "only those currently at this UTC offset, eg. +5:30",
+            },
+        ],
+        examples: &["tz zones kolkata", "tz zones --abbr --offset +5:30"],
+    },
+    Command {
+        name: "zone",
+        usage: "tz zone timezone",
+        summary: "everything about one timezone name",
+        description: "Gives the IANA timezone a name stands for, the time there
+now, its next change of UTC offset, its working hours, and
+its other names.",
+        flags: &[],
+        examples: &["tz zone ist"],
+    },
+    Command {
+        name: "watch",
+        usage: "tz watch timezone... [--interval 1s]",
+        summary: "the current time in several timezones, kept up to date",
+        description: "Redraws the current time in each timezone in place until
+ctrl-c, highlighting those that are on another day than the
+first.",
+        flags: &[Flag {
+            name: "--interval",
+            value: Some("duration"),
+            help: "how often to redraw, 1s by default",
+        }],
+        examples: &["tz watch pt et bst ist"],
+    },
+    Command {
+        name: "tui",
+        usage: "tz tui [timezone...]",
+        summary: "a full screen world clock and planner",
+        description: "Shows a row for each timezone, which are those given, or the
+destinations in the config file, or the local timezone, with
+the hours of a day in the first. The cursor moves along the
+day with the arrow keys, showing the time at it in every
+timezone, and [ and ] change the day. a adds a timezone,
+searching the names as they are typed, d removes the selected
+one, t goes back to now, and q quits.",
+        flags: &[],
+        examples: &["tz tui pt et bst ist"],
+    },
+    Command {
+        name: "logs",
+        usage: "tz logs [--from utc] [--to local] [--epoch] [--pattern regex] [--format format]",
+        summary: "rewrite the timestamps in a log into another timezone",
+        description: "Copies stdin to stdout, rewriting ISO 8601, syslog
+(Mar 10 14:30:00), and common log format timestamps, and with
+--epoch epoch timestamps, into another timezone.",
+        flags: &[
+            Flag {
+                name: "--from",
+                value: Some("timezone"),
+                help: "the timezone of timestamps without an offset, utc by default",
+            },
+            Flag {
+                name: "--to",
+                value: Some("timezone"),
+                help: "the timezone to rewrite them in, local by default",
+            },
+            Flag {
+                name: "--epoch",
+                value: None,
+                help: "also rewrite epochs, which are otherwise left as numbers",
+            },
+            Flag {
+                name: "--pattern",
+                value: Some("regex"),
+                help: "rewrite what this matches instead, or its group named time",
+            },
+            Flag {
+                name: "--format",
+                value: Some("format"),
+                help: "the strftime format of the timestamps matched by --pattern",
+            },
+        ],
+        examples: &["tail -f app.log | tz logs --from utc --to local"],
+    },
+    Command {
+        name: "--batch",
+        usage: "tz --batch [file] [--csv]",
+        summary: "convert one line at a time",
+        description: "Converts each line of the file, or of stdin without one,
+printing one line for each. Each line is a conversion, unless
+the first line is a header starting with time, or --csv is
+given, when each is a csv row of time,origin,destinations,date
+to which the converted times are added as columns. Fields with
+commas can be quoted. A line that cannot be converted gives an
+error in its place.",
+        flags: &[Flag {
+            name: "--csv",
+            value: None,
+            help: "read every line as a csv row, even without a header",
+        }],
+        examples: &["tz --batch meetings.csv"],
+    },
+    Command {
+        name: "--filter",
+        usage: "tz --filter [--to timezone...] [--date date]",
+        summary: "annotate the times mentioned in text",
+        description: "Copies stdin to stdout, adding the time in each destination
+after every time and timezone mentioned, eg.
+9:30am PT [10:00pm IST, 5:30pm BST]. Abbreviations have to be
+in upper case, so that 3pm at the cafe is left alone.",
+        flags: &[
+            Flag {
+                name: "--to",
+                value: Some("timezone..."),
+                help: "the destinations, otherwise those in the config file or local",
+            },
+            Flag {
+                name: "--date",
+                value: Some("date"),
+                help: "the date of the mentions, today by default",
+            },
+        ],
+        examples: &["tz --filter --to ist bst < notes.txt"],
+    },
+    Command {
+        name: "-i",
+        usage: "tz -i",
+        summary: "convert interactively",
+        description: "Reads conversions and commands one line at a time, with
+history and tab completion of timezones. set from et makes et
+the origin of each conversion after it, so that only the time
+and destinations are given, and set to bst ist sets the
+destinations used when none are given.",
+        flags: &[],
+        examples: &["tz -i"],
+    },
+    Command {
+        name: "completions",
+        usage: "tz completions bash|zsh|fish",
+        summary: "print a shell completion script",
+        description: "Prints a completion script for the subcommands, flags,
+timezones, people, and date words.",
+        flags: &[],
+        examples: &["source <(tz completions bash)"],
+    },
+    Command {
+        name: "man",
+        usage: "tz man",
+        summary: "print this help as a man page",
+        description: "Prints the man page, tz.1, in roff.",
+        flags: &[],
+        examples: &["tz man > /usr/local/share/man/man1/tz.1"],
+    },
+    Command {
+        name: "help",
+        usage: "tz help [command|config]",
+        summary: "the help of a command, or of the config file",
+        description: "Gives the help of a command, the same as its --help, or of
+the config file.",
+        flags: &[],
+        examples: &["tz help plan"],
+    },
+];
+
+pub static CONFIGURATION: &str = "The config file is $TIMEZONE_CONFIG, or else
+timezone/config.toml in $XDG_CONFIG_HOME or ~/.config, or the
+file given with --config.
+
+It can set the local timezone, the destinations used when a
+conversion gives none, a 12h or 24h clock or any strftime
+format, and whether to colour the output:
+
+\tlocal = \"Europe/London\"
+\tdestinations = [\"et\", \"ist\"]
+\tclock = \"24h\"
+\ttime_format = \"%H.%M\"
+\tcolor = \"never\"
+
+and add names for timezones, which take precedence over the
+built in ones:
+
+\t[aliases]
+\tcst = \"America/Chicago\"
+
+People and teams can be named, and used wherever a timezone
+can, as @name:
+
+\t[people]
+\talice = \"America/New_York\"
+\tbob = \"Europe/London\"
+
+\t[teams]
+\tlondon-team = [\"bob\"]
+
+Working hours default to 9am-5pm, monday to friday. They can
+be set for any timezone or person, or as the default, and an
+entry with a from date applies from that date onwards:
+
+\t[working_hours]
+\tdefault = { hours = \"9-17\" }
+\tist = { hours = \"10-19\" }
+\t\"Asia/Dubai\" = [
+\t    { weekend = [\"fri\", \"sat\"] },
+\t    { from = 2022-01-01, weekend = [\"sat\", \"sun\"] },
+\t]";
+
+// --batch, -i and batch all find the --batch command
+pub fn find_command(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|command| {
+        !command.name.is_empty()
+            && command.name.trim_start_matches('-') == name.trim_start_matches('-')
+    })
+}
+
+// the commands that are given as a word rather than a flag
+pub fn subcommands() -> Vec<&'static str> {
+    COMMANDS
+        .iter()
+        .map(|command| command.name)
+        .filter(|name| !name.is_empty() && !name.starts_with('-'))
+        .collect()
+}
+
+// every flag, including the commands given as one
+pub fn flags() -> Vec<&'static str> {
+    let mut flags: Vec<&str> = GLOBAL_FLAGS.iter().map(|flag| flag.name).collect();
+
+    for command in COMMANDS {
+        if command.name.starts_with('-') {
+            flags.push(command.name);
+        }
+        flags.extend(command.flags.iter().map(|flag| flag.name));
+    }
+
+    flags.sort();
+    flags.dedup();
+    flags
+}
+
+fn flag_usage(flag: &Flag) -> String {
+    match flag.value {
+        Some(value) => format!("{} {value}", flag.name),
+        None => flag.name.to_string(),
+    }
+}
+
+fn print_flags(flags: &[Flag]) {
+    let width = flags
+        .iter()
+        .map(|flag| flag_usage(flag).len())
+        .max()
+        .unwrap_or_default();
+
+    for flag in flags {
+        println!("\t{:<width$}  {}", flag_usage(flag), flag.help);
+    }
+}
+
+// the help of everything, for tz --help
+pub fn print_help() {
+    let conversion = &COMMANDS[0];
+
+    println!("\nTimezone conversion on the command line.\n");
+    println!("{}\n", "Usage:".bold());
+    println!("\t{}", conversion.usage);
+    println!("\ttz command [arguments]\n");
+    println!("{}\n", conversion.description);
+
+    println!("{}\n", "Commands:".bold());
+    let width = COMMANDS[1..]
+        .iter()
+        .map(|command| command.name.len())
+        .max()
+        .unwrap_or_default();
+    for command in &COMMANDS[1..] {
+        println!("\t{:<width$}  {}", command.name, command.summary);
+    }
+
+    println!("\n{}\n", "Options:".bold());
+    print_flags(GLOBAL_FLAGS);
+
+    println!("\n{}\n", "Examples:".bold());
+    for example in conversion.examples {
+        println!("\t{example}");
+    }
+
+    println!("\nUse tz help command for the details of a command, tz help config for the config file, and tz man for the man page.\n");
+}
+
+// the help of one command, for tz help diff and tz diff --help
+pub fn print_command_help(name: &str) {
+    if name == "config" {
+        return println!("\n{CONFIGURATION}\n");
+    }
+
+    let Some(command) = find_command(name) else {
+        return println!(
+            "\n[{}] unknown command {name}, expected one of: {}.\n",
+            "ERROR".red(),
+            COMMANDS[1..]
+                .iter()
+                .map(|command| command.name)
+                .collect::<Vec<&str>>()
+                .join(", ")
+        );
+    };
+
+    println!("\n{}\n", command.summary);
+    println!("{} {}\n", "Usage:".bold(), command.usage);
+    println!("{}\n", command.description);
+
+    if !command.flags.is_empty() {
+        println!("{}\n", "Options:".bold());
+        print_flags(command.flags);
+        println!();
+    }
+
+    println!("{}\n", "Examples:".bold());
+    for example in command.examples {
+        println!("\t{example}");
+    }
+    println!();
+}
+
+// escapes text for roff
+fn roff(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{escaped}")
+    } else {
+        escaped
+    }
+}
+
+// paragraphs of text, where those indented by tabs are examples
+fn roff_paragraphs(text: &str) -> String {
+    let mut page = String::new();
+
+    for paragraph in text.split("\n\n") {
+        let lines: Vec<&str> = paragraph.lines().collect();
+
+        if lines.iter().all(|line| line.starts_with('\t')) {
+            page += ".RS\n.nf\n";
+            for line in lines {
+                page += &format!("{}\n", roff(&line[1..]));
+            }
+            page += ".fi\n.RE\n";
+        } else {
+            page += ".PP\n";
+            for line in lines {
+                page += &format!("{}\n", roff(line));
+            }
+        }
+    }
+
+    page
+}
+
+fn roff_flags(flags: &[Flag]) -> String {
+    flags
+        .iter()
+        .map(|flag| {
+            let value = match flag.value {
+                Some(value) => format!(" \\fI{}\\fR", roff(value)),
+                None => String::new(),
+            };
+            format!(
+                ".TP\n\\fB{}\\fR{value}\n{}\n",
+                roff(flag.name),
+                roff(flag.help)
+            )
+        })
+        .collect()
+}
+
+// tz.1
+pub fn man_page() -> String {
+    let conversion = &COMMANDS[0];
+    let mut page = format!(
+        ".TH TZ 1 \"\" \"timezone {}\" \"User Commands\"\n",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    page += ".SH NAME\ntz \\- timezone conversion on the command line\n";
+    page += &format!(
+        ".SH SYNOPSIS\n{}\n.br\ntz \\fIcommand\\fR [\\fIarguments\\fR]\n",
+        roff(conversion.usage)
+    );
+    page += ".SH DESCRIPTION\n";
+    page += &roff_paragraphs(conversion.description);
+    page += ".SH OPTIONS\n";
+    page += &roff_flags(GLOBAL_FLAGS);
+
+    page += ".SH COMMANDS\n";
+    for command in &COMMANDS[1..] {
+        page += &format!(".SS {}\n", roff(command.usage));
+        page += &format!("{}.\n", roff(command.summary));
+        page += &roff_paragraphs(command.description);
+        page += &roff_flags(command.flags);
+    }
+
+    page += ".SH CONFIGURATION\n";
+    page += &roff_paragraphs(CONFIGURATION);
+
+    page += ".SH ENVIRONMENT\n";
+    page += &roff_flags(&[
+        Flag {
+            name: "TIMEZONE_CONFIG",
+            value: None,
+            help: "the config file",
+        },
+        Flag {
+            name: "XDG_CONFIG_HOME",
+            value: None,
+            help: "the directory of timezone/config.toml, ~/.config by default",
+        },
+        Flag {
+            name: "TZ",
+            value: None,
+            help: "the local timezone, unless it is set in the config file",
+        },
+        Flag {
+            name: "NO_COLOR",
+            value: None,
+            help: "no colour, unless --color always is given",
+        },
+        Flag {
+            name: "CLICOLOR_FORCE",
+            value: None,
+            help: "colour even when not writing to a terminal",
+        },
+        Flag {
+            name: "LC_ALL, LC_TIME, LANG",
+            value: None,
+            help: "the language of dates, and whether numeric dates are read month first",
+        },
+    ]);
+
+    page += ".SH EXAMPLES\n.nf\n";
+    for command in COMMANDS {
+        for example in command.examples {
+            page += &format!("{}\n", roff(example));
+        }
+    }
+    page += ".fi\n";
+
+    page
+}
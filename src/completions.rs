@@ -0,0 +1,198 @@
+use crate::{flags, subcommands, Settings, ENGLISH, LOCALES, TZ_MAP};
+use colored::*;
+
+const SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+// the names the binary may be installed as
+const COMMANDS: [&str; 2] = ["tz", "timezone"];
+
+// every timezone, person and team, as they would be typed
+fn zones(settings: &Settings) -> Vec<String> {
+    let mut zones: Vec<String> = TZ_MAP.keys().map(|key| key.to_string()).collect();
+    zones.extend(settings.config.aliases().map(|name| name.to_string()));
+    zones.extend(settings.config.people.keys().map(|name| format!("@{name}")));
+    zones.extend(settings.config.teams.keys().map(|name| format!("@{name}")));
+    zones.sort();
+    zones.dedup();
+    zones
+}
+
+// month names, weekdays and words such as tomorrow, in English
+// and the chosen language, leaving out those of several words
+fn date_words(settings: &Settings) -> Vec<String> {
+    let mut words = vec![];
+
+    for locale in [&ENGLISH, settings.locale] {
+        words.extend(locale.months.keys());
+        words.extend(locale.weekdays.keys());
+        words.extend(locale.days.keys());
+    }
+    words.extend(["next", "last", "in", "now"]);
+
+    let mut words: Vec<String> = words
+        .into_iter()
+        .filter(|word| word.chars().all(char::is_alphabetic))
+        .map(|word| word.to_string())
+        .collect();
+    words.sort();
+    words.dedup();
+    words
+}
+
+fn languages() -> Vec<&'static str> {
+    let mut languages: Vec<&str> = LOCALES.keys().copied().collect();
+    languages.sort();
+    languages
+}
+
+fn bash(settings: &Settings) -> String {
+    format!(
+        r#"_tz() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    case "$prev" in
+        --lang) COMPREPLY=($(compgen -W "{languages}" -- "$cur")); return ;;
+        --date-order) COMPREPLY=($(compgen -W "dmy mdy" -- "$cur")); return ;;
+        --color) COMPREPLY=($(compgen -W "auto always never" -- "$cur")); return ;;
+        --config|--batch) COMPREPLY=($(compgen -f -- "$cur")); return ;;
+        completions) COMPREPLY=($(compgen -W "{shells}" -- "$cur")); return ;;
+    esac
+
+    local words="{flags} {zones} {dates}"
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        words="{subcommands} $words"
+    fi
+
+    COMPREPLY=($(compgen -W "$words" -- "$cur"))
+}}
+
+complete -F _tz {commands}
+"#,
+        languages = languages().join(" "),
+        shells = SHELLS.join(" "),
+        flags = flags().join(" "),
+        zones = zones(settings).join(" "),
+        dates = date_words(settings).join(" "),
+        subcommands = subcommands().join(" "),
+        commands = COMMANDS.join(" "),
+    )
+}
+
+fn zsh(settings: &Settings) -> String {
+    format!(
+        r#"#compdef {commands}
+
+_tz() {{
+    local -a subcommands flags zones dates
+    subcommands=({subcommands})
+    flags=({flags})
+    zones=({zones})
+    dates=({dates})
+
+    case "$words[CURRENT-1]" in
+        --lang) compadd -- {languages}; return ;;
+        --date-order) compadd -- dmy mdy; return ;;
+        --color) compadd -- auto always never; return ;;
+        --config|--batch) _files; return ;;
+        completions) compadd -- {shells}; return ;;
+    esac
+
+    if (( CURRENT == 2 )); then
+        compadd -- $subcommands
+    fi
+    compadd -- $flags $zones $dates
+}}
+
+if [ "$funcstack[1]" = "_tz" ]; then
+    _tz "$@"
+else
+    compdef _tz {commands}
+fi
+"#,
+        commands = COMMANDS.join(" "),
+        subcommands = subcommands().join(" "),
+        flags = flags().join(" "),
+        zones = zones(settings)
+            .iter()
+            .map(|zone| format!("'{zone}'"))
+            .collect::<Vec<String>>()
+            .join(" "),
+        dates = date_words(settings).join(" "),
+        languages = languages().join(" "),
+        shells = SHELLS.join(" "),
+    )
+}
+
+fn fish(settings: &Settings) -> String {
+    let mut script = String::new();
+
+    for command in COMMANDS {
+        let flags = flags();
+        let long_flags: Vec<&str> = flags
+            .iter()
+            .filter_map(|flag| flag.strip_prefix("--"))
+            .collect();
+
+        script += &format!(
+            "complete -c {command} -f
+complete -c {command} -n __fish_use_subcommand -a '{subcommands}'
+complete -c {command} -n '__fish_seen_subcommand_from completions' -a '{shells}'
+complete -c {command} -l lang -x -a '{languages}'
+complete -c {command} -l date-order -x -a 'dmy mdy'
+complete -c {command} -l color -x -a 'auto always never'
+complete -c {command} -l config -r -F
+complete -c {command} -l batch -r -F
+complete -c {command} -s i -l interactive
+",
+            subcommands = subcommands().join(" "),
+            shells = SHELLS.join(" "),
+            languages = languages().join(" "),
+        );
+
+        for flag in long_flags {
+            if ![
+                "lang",
+                "date-order",
+                "color",
+                "config",
+                "batch",
+                "interactive",
+            ]
+            .contains(&flag)
+            {
+                script += &format!("complete -c {command} -l {flag}\n");
+            }
+        }
+
+        script += &format!(
+            "complete -c {command} -a '{}'\ncomplete -c {command} -a '{}'\n",
+            zones(settings).join(" "),
+            date_words(settings).join(" "),
+        );
+    }
+
+    script
+}
+
+/*
+ * bash - source <(tz completions bash)
+ * zsh  - tz completions zsh > "${fpath[1]}/_tz"
+ * fish - tz completions fish > ~/.config/fish/completions/tz.fish
+ */
+pub fn completions(args: Vec<String>, settings: &Settings) {
+    let script = match args.first().map(|shell| shell.as_str()) {
+        Some("bash") => bash(settings),
+        Some("zsh") => zsh(settings),
+        Some("fish") => fish(settings),
+        _ => {
+            return println!(
+                "\n[{}] expected the shell to complete for, one of: {}.\n",
+                "ERROR".red(),
+                SHELLS.join(", ")
+            );
+        }
+    };
+
+    print!("{script}");
+}
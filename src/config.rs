@@ -0,0 +1,598 @@
+use crate::{lookup_timezone, parse_hours, Clock, DateOrder, Locale, WorkingHours, ENGLISH};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use chrono_tz::Tz;
+use colored::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/*
+ * local = "Europe/London"
+ * destinations = ["et", "ist"]
+ * clock = "24h"
+ * time_format = "%H.%M"
+ * color = "never"
+ *
+ * [aliases]
+ * cst = "America/Chicago"
+ * office = "Europe/Berlin"
+ *
+ * [people]
+ * alice = "America/New_York"
+ * bob = "bst"
+ *
+ * [teams]
+ * london-team = ["bob", "carol"]
+ *
+ * [working_hours]
+ * default = { hours = "9-17" }
+ * "Asia/Kolkata" = { hours = "10-19" }
+ * "Asia/Dubai" = [
+ *     { weekend = ["fri", "sat"] },
+ *     { from = 2022-01-01, weekend = ["sat", "sun"] },
+ * ]
+ */
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    local: Option<String>,
+    destinations: Vec<String>,
+    clock: Option<String>,
+    time_format: Option<String>,
+    color: Option<String>,
+    aliases: HashMap<String, String>,
+    people: HashMap<String, String>,
+    teams: HashMap<String, Vec<String>>,
+    working_hours: HashMap<String, OneOrMany<WorkingHoursEntry>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(one) => vec![one],
+            OneOrMany::Many(many) => many,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkingHoursEntry {
+    from: Option<toml::value::Datetime>,
+    hours: Option<String>,
+    weekend: Option<Vec<String>>,
+}
+
+// working hours that apply from a date onwards, leaving out
+// anything that is not being changed
+#[derive(Clone, Debug, Default, PartialEq)]
+struct WorkingHoursRule {
+    from: Option<NaiveDate>,
+    hours: Option<(NaiveTime, NaiveTime)>,
+    weekend: Option<Vec<Weekday>>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    pub local: Option<String>,     // instead of guessing the local timezone
+    pub destinations: Vec<String>, // when a conversion gives none
+    pub clock: Clock,
+    pub color: Option<bool>,             // None to leave it to the terminal
+    aliases: HashMap<String, String>,    // name -> timezone, over TZ_MAP
+    pub people: HashMap<String, String>, // name -> timezone
+    pub teams: HashMap<String, Vec<String>>, // name -> people
+    working_hours: HashMap<String, Vec<WorkingHoursRule>>,
+}
+
+// everything that is chosen by the user rather than given as
+// part of a conversion
+pub struct Settings {
+    pub locale: &'static Locale,
+    pub order: DateOrder,
+    pub config: Config,
+    pub origin: Option<String>, // set from, in interactive mode
+    pub quiet: bool,            // only the converted times
+    pub warnings: bool,         // false with --no-warnings or --quiet
+}
+
+// $TIMEZONE_CONFIG, or else config.toml in $XDG_CONFIG_HOME/timezone
+// or ~/.config/timezone
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("TIMEZONE_CONFIG").filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+
+    let config_home = match std::env::var_os("XDG_CONFIG_HOME").filter(|path| !path.is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(config_home.join("timezone/config.toml"))
+}
+
+impl Config {
+    // the config file if there is one, warning about and ignoring
+    // anything in it that cannot be understood. A file given with
+    // --config has to exist.
+    pub fn load(given: Option<String>) -> Result<Config, String> {
+        let path = match (given, config_path()) {
+            (Some(given), _) if !PathBuf::from(&given).exists() => {
+                return Err(format!("cannot find the config file {given}."));
+            }
+            (Some(given), _) => PathBuf::from(given),
+            (None, Some(path)) if path.exists() => path,
+            _ => return Ok(Config::default()),
+        };
+
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|contents| {
+                toml::from_str::<ConfigFile>(&contents).map_err(|error| error.to_string())
+            });
+
+        Ok(match parsed {
+            Ok(file) => Config::from_file(file),
+            Err(error) => {
+                println!(
+                    "\n[{}] cannot read config file {}, ignoring it: {error}",
+                    "WARNING".yellow(),
+                    path.display()
+                );
+                Config::default()
+            }
+        })
+    }
+
+    fn from_file(file: ConfigFile) -> Config {
+        let mut config = Config::default();
+
+        for (name, zone) in file.aliases {
+            if lookup_timezone(&zone).is_some() {
+                config.aliases.insert(name.to_lowercase(), zone);
+            } else {
+                println!(
+                    "\n[{}] cannot parse the timezone {zone} of the alias {name} in the config file, ignoring it.",
+                    "WARNING".yellow()
+                );
+            }
+        }
+
+        if let Some(local) = file.local {
+            if config.lookup_timezone(&local).is_some() {
+                config.local = Some(local);
+            } else {
+                println!(
+                    "\n[{}] cannot parse the local timezone {local} in the config file, ignoring it.",
+                    "WARNING".yellow()
+                );
+            }
+        }
+
+        for destination in file.destinations {
+            if config.lookup_timezone(&destination).is_some() {
+                config.destinations.push(destination);
+            } else {
+                println!(
+                    "\n[{}] cannot parse the destination {destination} in the config file, ignoring it.",
+                    "WARNING".yellow()
+                );
+            }
+        }
+
+        if let Some(clock) = file.clock {
+            match Clock::parse(&clock) {
+                Some(clock) => config.clock = clock,
+                None => println!(
+                    "\n[{}] unknown clock \"{clock}\" in the config file, expected 12h or 24h.",
+                    "WARNING".yellow()
+                ),
+            }
+        }
+
+        if let Some(format) = file.time_format {
+            if StrftimeItems::new(&format).any(|item| item == Item::Error) {
+                println!(
+                    "\n[{}] cannot parse the time format \"{format}\" in the config file, ignoring it.",
+                    "WARNING".yellow()
+                );
+            } else {
+                config.clock = Clock::Custom(format);
+            }
+        }
+
+        if let Some(color) = file.color {
+            match color.as_str() {
+                "always" => config.color = Some(true),
+                "never" => config.color = Some(false),
+                "auto" => {}
+                _ => println!(
+                    "\n[{}] unknown color \"{color}\" in the config file, expected auto, always, or never.",
+                    "WARNING".yellow()
+                ),
+            }
+        }
+
+        for (name, zone) in file.people {
+            if config.lookup_timezone(&zone).is_some() {
+                config.people.insert(name, zone);
+            } else {
+                println!(
+                    "\n[{}] cannot parse the timezone {zone} of {name} in the config file, ignoring them.",
+                    "WARNING".yellow()
+                );
+            }
+        }
+
+        for (name, members) in file.teams {
+            if members.is_empty() {
+                println!(
+                    "\n[{}] the team {name} in the config file has no one in it, ignoring the team.",
+                    "WARNING".yellow()
+                );
+                continue;
+            }
+
+            match members.iter().find(|member| !config.people.contains_key(*member)) {
+                Some(unknown) => println!(
+                    "\n[{}] {unknown} in the team {name} is not one of the people in the config file, ignoring the team.",
+                    "WARNING".yellow()
+                ),
+                None => {
+                    config.teams.insert(name, members);
+                }
+            }
+        }
+
+        for (name, entries) in file.working_hours {
+            let mut rules = vec![];
+
+            for entry in entries.into_vec() {
+                match WorkingHoursRule::parse(entry) {
+                    Some(rule) => rules.push(rule),
+                    None => println!(
+                        "\n[{}] cannot parse the working hours of {name} in the config file, ignoring them.",
+                        "WARNING".yellow()
+                    ),
+                }
+            }
+
+            rules.sort_by_key(|rule| rule.from);
+            config.working_hours.insert(name, rules);
+        }
+
+        config
+    }
+
+    // @alice is a person, anything else is an alias or else looked
+    // up in TZ_MAP
+    pub fn lookup_timezone(&self, name: &str) -> Option<Tz> {
+        match name.strip_prefix('@') {
+            Some(person) => self.lookup_timezone(self.people.get(person)?),
+            None => match self.aliases.get(&name.to_lowercase()) {
+                Some(zone) => lookup_timezone(zone),
+                None => lookup_timezone(name),
+            },
+        }
+    }
+
+    // the names added to TZ_MAP
+    pub fn aliases(&self) -> impl Iterator<Item = &String> {
+        self.aliases.keys()
+    }
+
+    // replaces each @team with its people, @alice @bob
+    pub fn expand_teams(&self, args: Vec<String>) -> Vec<String> {
+        args.into_iter()
+            .flat_map(
+                |arg| match arg.strip_prefix('@').and_then(|name| self.teams.get(name)) {
+                    Some(members) => members.iter().map(|member| format!("@{member}")).collect(),
+                    None => vec![arg],
+                },
+            )
+            .collect()
+    }
+
+    // the working hours configured for `name` (a timezone or @person)
+    // on `date`, by name, or else by an entry naming the same
+    // timezone, on top of the default entry. An entry for a person
+    // only applies to @person. None when nothing applies at all.
+    pub fn configured_working_hours(&self, name: &str, date: NaiveDate) -> Option<WorkingHours> {
+        let timezone: Option<Tz> = self.lookup_timezone(name);
+        let is_person = |key: &str| self.people.contains_key(key);
+
+        let specific = match name.strip_prefix('@') {
+            Some(person) => self.working_hours.get(person),
+            None => self.working_hours.get(name).filter(|_| !is_person(name)),
+        }
+        .or_else(|| {
+            let timezone = timezone?;
+
+            // the entry under the IANA name, or else the first by
+            // name, so that the same entry applies every time
+            self.working_hours
+                .iter()
+                .filter(|(key, _)| {
+                    *key != "default"
+                        && !is_person(key)
+                        && self.lookup_timezone(key) == Some(timezone)
+                })
+                .min_by_key(|(key, _)| (key.as_str() != timezone.name(), key.as_str()))
+                .map(|(_, rules)| rules)
+        });
+        let default = self.working_hours.get("default");
+
+        if specific.is_none() && default.is_none() {
+            return None;
+        }
+
+        let mut hours = WorkingHours::default();
+
+        for rules in [default, specific].into_iter().flatten() {
+            for rule in rules
+                .iter()
+                .filter(|rule| rule.from.is_none_or(|from| from <= date))
+            {
+                if let Some((start, end)) = rule.hours {
+                    hours.start = start;
+                    hours.end = end;
+                }
+
+                if let Some(weekend) = &rule.weekend {
+                    hours.weekend = weekend.clone();
+                }
+            }
+        }
+
+        Some(hours)
+    }
+
+    // a config file with these contents, for tests
+    #[cfg(test)]
+    pub fn from_toml(contents: &str) -> Config {
+        Config::from_file(toml::from_str(contents).unwrap())
+    }
+
+    // as configured, or 9am-5pm monday to friday
+    pub fn working_hours(&self, name: &str, date: NaiveDate) -> WorkingHours {
+        self.configured_working_hours(name, date)
+            .unwrap_or_default()
+    }
+}
+
+impl WorkingHoursRule {
+    fn parse(entry: WorkingHoursEntry) -> Option<WorkingHoursRule> {
+        let from = match entry.from {
+            Some(datetime) => {
+                let date = datetime.date?;
+                Some(NaiveDate::from_ymd_opt(
+                    date.year.into(),
+                    date.month.into(),
+                    date.day.into(),
+                )?)
+            }
+            None => None,
+        };
+
+        let hours = match entry.hours {
+            Some(hours) => Some(parse_hours(hours)?),
+            None => None,
+        };
+
+        let weekend = match entry.weekend {
+            Some(days) => Some(
+                days.iter()
+                    .map(|day| ENGLISH.weekday(&day.to_lowercase()))
+                    .collect::<Option<Vec<Weekday>>>()?,
+            ),
+            None => None,
+        };
+
+        Some(WorkingHoursRule {
+            from,
+            hours,
+            weekend,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{config, date};
+
+    fn hour(hour: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn entries_for_the_same_timezone_prefer_the_iana_name() {
+        let config = config(
+            r#"
+            [people]
+            priya = "Asia/Kolkata"
+
+            [working_hours]
+            ist = { hours = "10-19" }
+            "Asia/Kolkata" = { hours = "8-12" }
+            "#,
+        );
+
+        for name in ["@priya", "Asia/Kolkata"] {
+            let hours = config.working_hours(name, date(2025, 3, 5));
+            assert_eq!((hours.start, hours.end), (hour(8), hour(12)), "{name}");
+        }
+
+        let hours = config.working_hours("ist", date(2025, 3, 5));
+        assert_eq!((hours.start, hours.end), (hour(10), hour(19)));
+    }
+
+    #[test]
+    fn entries_without_the_iana_name_take_the_first_by_name() {
+        let config = config(
+            r#"
+            [aliases]
+            india = "Asia/Kolkata"
+
+            [working_hours]
+            ist = { hours = "10-19" }
+            india = { hours = "8-12" }
+            "#,
+        );
+
+        let hours = config.working_hours("Asia/Kolkata", date(2025, 3, 5));
+        assert_eq!((hours.start, hours.end), (hour(8), hour(12)));
+    }
+
+    #[test]
+    fn rules_apply_from_their_date() {
+        let config = config(
+            r#"
+            [working_hours]
+            default = { hours = "9-17" }
+            "Asia/Dubai" = [
+                { weekend = ["fri", "sat"] },
+                { from = 2022-01-01, weekend = ["sat", "sun"] },
+            ]
+            "#,
+        );
+
+        let before = config.working_hours("Asia/Dubai", date(2021, 12, 31));
+        let after = config.working_hours("Asia/Dubai", date(2022, 1, 1));
+
+        assert_eq!(before.weekend, vec![Weekday::Fri, Weekday::Sat]);
+        assert_eq!(after.weekend, vec![Weekday::Sat, Weekday::Sun]);
+        assert_eq!((after.start, after.end), (hour(9), hour(17)));
+        assert_eq!(
+            config
+                .configured_working_hours("et", date(2022, 1, 1))
+                .map(|h| h.weekend),
+            Some(vec![Weekday::Sat, Weekday::Sun])
+        );
+    }
+
+    #[test]
+    fn entries_for_people_only_apply_to_them() {
+        let config = config(
+            r#"
+            [people]
+            bob = "Europe/London"
+            carol = "Europe/London"
+
+            [working_hours]
+            bob = { hours = "8-16" }
+            "#,
+        );
+
+        let hours = config.working_hours("@bob", date(2025, 3, 5));
+        assert_eq!((hours.start, hours.end), (hour(8), hour(16)));
+
+        for name in ["@carol", "Europe/London", "bob"] {
+            let hours = config.working_hours(name, date(2025, 3, 5));
+            assert_eq!((hours.start, hours.end), (hour(9), hour(17)), "{name}");
+        }
+    }
+
+    #[test]
+    fn teams_expand_to_their_people() {
+        let config = config(
+            r#"
+            [people]
+            alice = "America/New_York"
+            bob = "Europe/London"
+
+            [teams]
+            design = ["alice", "bob"]
+            nobody = []
+            "#,
+        );
+
+        assert_eq!(
+            config.expand_teams(vec!["9am".into(), "@design".into(), "ist".into()]),
+            ["9am", "@alice", "@bob", "ist"]
+        );
+        assert_eq!(config.expand_teams(vec!["@nobody".into()]), ["@nobody"]);
+        assert_eq!(config.expand_teams(vec!["@alice".into()]), ["@alice"]);
+    }
+
+    #[test]
+    fn people_are_looked_up_with_an_at() {
+        let config = config(
+            r#"
+            [people]
+            bob = "Europe/London"
+            "#,
+        );
+
+        assert_eq!(
+            config.lookup_timezone("@bob"),
+            Some(chrono_tz::Europe::London)
+        );
+        assert_eq!(config.lookup_timezone("bob"), None);
+        assert_eq!(config.lookup_timezone("@carol"), None);
+    }
+
+    #[test]
+    fn a_missing_config_file_is_an_error() {
+        assert!(Config::load(Some("/nonexistent/config.toml".to_string())).is_err());
+    }
+
+    #[test]
+    fn time_formats_replace_the_clock() {
+        let format = config(r#"time_format = "%H.%M""#);
+        assert_eq!(format.clock, Clock::Custom("%H.%M".to_string()));
+
+        // a format that cannot be parsed leaves the clock as it was
+        let broken = config(
+            r#"
+            clock = "24h"
+            time_format = "%H.%"
+            "#,
+        );
+        assert_eq!(broken.clock, Clock::TwentyFourHour);
+    }
+
+    #[test]
+    fn aliases_take_over_the_usual_names() {
+        let config = config(
+            r#"
+            [aliases]
+            IST = "Asia/Jerusalem"
+            office = "Europe/Berlin"
+            cst = "Nowhere/Special"
+            "#,
+        );
+
+        assert_eq!(
+            config.lookup_timezone("ist"),
+            Some(chrono_tz::Asia::Jerusalem)
+        );
+        assert_eq!(
+            config.lookup_timezone("Office"),
+            Some(chrono_tz::Europe::Berlin)
+        );
+        assert_eq!(config.lookup_timezone("cst"), lookup_timezone("cst"));
+    }
+
+    #[test]
+    fn local_has_to_be_a_timezone() {
+        let office = config(
+            r#"
+            local = "office"
+
+            [aliases]
+            office = "Europe/Berlin"
+            "#,
+        );
+        assert_eq!(office.local.as_deref(), Some("office"));
+
+        assert_eq!(config(r#"local = "nowhere""#).local, None);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{format_duration, parse_zoned_time, today, Settings, WorkingHours};
+use chrono::{DateTime, Datelike, Duration, TimeZone};
+use chrono_tz::Tz;
+use colored::*;
+
+// how much of the time between `from` and `to` falls within
+// `hours` as they are kept in `timezone`. Negative when `to`
+// is before `from`.
+pub fn business_hours(
+    from: DateTime<Tz>,
+    to: DateTime<Tz>,
+    timezone: Tz,
+    hours: &WorkingHours,
+) -> Duration {
+    if to < from {
+        return -business_hours(to, from, timezone, hours);
+    }
+
+    let (from, to) = (from.with_timezone(&timezone), to.with_timezone(&timezone));
+    let mut total = Duration::zero();
+    let mut day = from.date_naive();
+
+    while day <= to.date_naive() {
+        if !hours.weekend.contains(&day.weekday()) {
+            let maybe_window = (
+                timezone
+                    .from_local_datetime(&day.and_time(hours.start))
+                    .earliest(),
+                timezone
+                    .from_local_datetime(&day.and_time(hours.end))
+                    .earliest(),
+            );
+
+            if let (Some(start), Some(end)) = maybe_window {
+                let overlap = end.min(to) - start.max(from);
+
+                if overlap > Duration::zero() {
+                    total += overlap;
+                }
+            }
+        }
+
+        day = match day.succ_opt() {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    total
+}
+
+/*
+ * 9am pt friday -- 10am ist monday - elapsed time from the first to the second
+ * now pt -- 5pm bst                - now can be used for either
+ *
+ * the date of the second time is relative to the first, so that
+ * friday -- monday is the monday after that friday
+ */
+pub fn diff(args: Vec<String>, settings: &Settings) {
+    let maybe_split = args.iter().position(|arg| arg == "--");
+
+    let (first, second) = match maybe_split {
+        Some(i) if i >= 2 && args.len() - i > 2 => (&args[..i], &args[i + 1..]),
+        _ => {
+            println!(
+                "\n[{}] expected two times to compare.\n\n\tUsage: tz diff time timezone date -- time timezone date\n\tExample: tz diff 9am pt friday -- 10am ist monday\n",
+                "ERROR".red()
+            );
+            return;
+        }
+    };
+
+    let mut times: Vec<DateTime<Tz>> = vec![];
+
+    for side in [first, second] {
+        let relative_to = match times.first() {
+            Some(time) => time.date_naive(),
+            None => today(),
+        };
+
+        let maybe_time = settings
+            .config
+            .lookup_timezone(&side[1])
+            .and_then(|timezone| {
+                parse_zoned_time(
+                    side[0].clone(),
+                    timezone,
+                    if side.len() > 2 {
+                        Some(side[2..].join(" "))
+                    } else {
+                        None
+                    },
+                    settings.locale,
+                    settings.order,
+                    relative_to,
+                )
+            });
+
+        match maybe_time {
+            Some(time) => times.push(time),
+            None => {
+                println!(
+                    "\n[{}] cannot parse \"{}\"\n",
+                    "ERROR".red(),
+                    side.join(" ")
+                );
+                return;
+            }
+        }
+    }
+
+    let (from, to) = (times[0], times[1]);
+    let from_hours = settings.config.working_hours(&first[1], from.date_naive());
+    let to_hours = settings.config.working_hours(&second[1], to.date_naive());
+
+    println!("\n");
+    println!(
+        "{} to {} is {}",
+        first.join(" "),
+        second.join(" "),
+        format_duration(to - from).cyan()
+    );
+
+    let from_business = business_hours(from, to, from.timezone(), &from_hours);
+
+    if from.timezone() == to.timezone() && from_hours == to_hours {
+        println!(
+            "of which business hours are {} in {} ({from_hours})",
+            format_duration(from_business),
+            first[1]
+        );
+    } else {
+        println!(
+            "of which business hours are {} in {} ({from_hours}), and {} in {} ({to_hours})",
+            format_duration(from_business),
+            first[1],
+            format_duration(business_hours(from, to, to.timezone(), &to_hours)),
+            second[1]
+        );
+    }
+    println!("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{date, zone};
+
+    fn at(timezone: Tz, day: u32, hour: u32) -> DateTime<Tz> {
+        timezone
+            .from_local_datetime(&date(2025, 6, day).and_hms_opt(hour, 0, 0).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn business_hours_across_a_weekend() {
+        let pt = zone("America/Los_Angeles");
+        let ist = zone("Asia/Kolkata");
+        let hours = WorkingHours::default();
+
+        // 9am pt friday to 10am ist monday
+        let (from, to) = (at(pt, 6, 9), at(ist, 9, 10));
+
+        assert_eq!(to - from, Duration::minutes(60 * 60 + 30));
+        assert_eq!(business_hours(from, to, pt, &hours), Duration::hours(8));
+        assert_eq!(business_hours(from, to, ist, &hours), Duration::hours(1));
+        assert_eq!(business_hours(to, from, ist, &hours), Duration::hours(-1));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::offset::display_name;
+use crate::{
+    convert, local_timezone, parse_time, stream_error, take_date_option, Conversion, Settings,
+};
+use colored::*;
+use regex::{Captures, Regex};
+use std::io::{BufRead, Write};
+
+// 9:30am PT, 10 p.m. Europe/London, 14:00 CET
+const MENTION: &str = r"(?i)\b(\d{1,2}(?::\d{2})?\s?(?:am|pm|a\.m\.|p\.m\.)|\d{1,2}:\d{2})\s+(@?[a-z][a-z0-9_/+\-]*[a-z0-9])";
+
+// whether a word can be a timezone in running text, where lower case
+// abbreviations such as "at" or "in" are usually just words: an IANA
+// name, a @person, or an abbreviation in upper case
+fn looks_like_zone(word: &str) -> bool {
+    word.contains('/') || word.starts_with('@') || word == word.to_uppercase()
+}
+
+// 10 p.m. -> 10pm
+fn normalise_time(time: &str) -> String {
+    time.to_lowercase().replace(['.', ' '], "")
+}
+
+// the mention with the time in each destination after it, or
+// None when it is not a time and timezone after all
+fn annotate(
+    captures: &Captures,
+    destinations: &[String],
+    date: &Option<String>,
+    settings: &Settings,
+) -> Option<String> {
+    let time = normalise_time(&captures[1]);
+    let zone = captures[2].to_string();
+
+    parse_time(time.clone())?;
+    if !looks_like_zone(&zone) {
+        return None;
+    }
+    let origin = settings.config.lookup_timezone(&zone)?;
+
+    // the conversion would be to itself
+    let destinations: Vec<String> = destinations
+        .iter()
+        .filter(|destination| settings.config.lookup_timezone(destination) != Some(origin))
+        .cloned()
+        .collect();
+
+    if destinations.is_empty() {
+        return None;
+    }
+
+    let conversion = Conversion {
+        time,
+        origin: Some(zone),
+        destinations,
+        adjustments: vec![],
+        date: date.clone(),
+    };
+    let (_, converted) = convert(conversion, settings).ok()?;
+
+    let annotations: Vec<String> = converted
+        .into_iter()
+        .map(|converted| {
+            let destination = converted.destination.unwrap_or("local time".to_string());
+            format!("{} {}", converted.time, display_name(&destination))
+        })
+        .collect();
+
+    Some(format!(
+        "{} {}",
+        &captures[0],
+        format!("[{}]", annotations.join(", ")).cyan()
+    ))
+}
+
+// the line with every mention annotated, leaving those that cannot
+// be converted as they are
+fn annotate_line(
+    line: &str,
+    mention: &Regex,
+    destinations: &[String],
+    date: &Option<String>,
+    settings: &Settings,
+) -> String {
+    mention
+        .replace_all(line, |captures: &Captures| {
+            annotate(captures, destinations, date, settings).unwrap_or(captures[0].to_string())
+        })
+        .to_string()
+}
+
+/*
+ * --to ist bst      - the timezones to annotate each mention with,
+ *                     otherwise the configured destinations or local
+ * --date tomorrow   - the date of the mentions, otherwise today
+ */
+pub fn filter(mut args: Vec<String>, settings: &Settings) {
+    let date = take_date_option(&mut args, "--date", settings);
+
+    let destinations: Vec<String> = match args.iter().position(|arg| arg == "--to") {
+        Some(start) => args[start + 1..]
+            .iter()
+            .take_while(|arg| !arg.starts_with("--"))
+            .cloned()
+            .collect(),
+        None => settings.config.destinations.clone(),
+    };
+    let destinations = settings.config.expand_teams(destinations);
+
+    if let Some(unknown) = destinations
+        .iter()
+        .find(|destination| settings.config.lookup_timezone(destination).is_none())
+    {
+        return println!("\n[{}] cannot parse destination {unknown}\n", "ERROR".red());
+    }
+
+    let destinations = if destinations.is_empty() {
+        vec![settings.config.local.clone().unwrap_or_else(local_timezone)]
+    } else {
+        destinations
+    };
+
+    let mention = Regex::new(MENTION).unwrap();
+    let mut stdout = std::io::stdout().lock();
+
+    for line in std::io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => return println!("\n[{}] {error}\n", "ERROR".red()),
+        };
+
+        let annotated = annotate_line(&line, &mention, &destinations, &date, settings);
+        if let Err(error) = writeln!(stdout, "{annotated}") {
+            return stream_error(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::settings;
+
+    fn annotated(line: &str, destination: &str, date: &str) -> String {
+        annotate_line(
+            line,
+            &Regex::new(MENTION).unwrap(),
+            &[destination.to_string()],
+            &Some(date.to_string()),
+            &settings(),
+        )
+    }
+
+    #[test]
+    fn mentions_are_annotated() {
+        let line = annotated("call at 9:30am PT", "ist", "2025-06-04");
+
+        assert!(line.starts_with("call at 9:30am PT "));
+        assert!(line.contains("10:00pm"));
+    }
+
+    #[test]
+    fn mentions_that_cannot_be_converted_are_left_as_they_are() {
+        // the clocks go forward past 2:30am in New York on this day
+        assert_eq!(annotated("x 2:30am ET", "bst", "2025-03-09"), "x 2:30am ET");
+        assert_eq!(
+            annotated("at 9:30am nowhere", "bst", "2025-03-09"),
+            "at 9:30am nowhere"
+        );
+    }
+
+    #[test]
+    fn words_after_a_time_are_not_taken_for_abbreviations() {
+        assert_eq!(
+            annotated("at 3pm at the cafe", "bst", "2025-06-04"),
+            "at 3pm at the cafe"
+        );
+        assert!(annotated("at 3pm UTC at the cafe", "bst", "2025-06-04").contains("4:00pm"));
+        assert!(annotated("at 3pm Europe/London", "ist", "2025-06-04").contains("7:30pm"));
+    }
+}
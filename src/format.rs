@@ -0,0 +1,50 @@
+use crate::Clock;
+use chrono::{DateTime, Duration, Timelike};
+use chrono_tz::Tz;
+use colored::*;
+
+// 1pm -> 1:00pm, or 13:00 on a 24 hour clock
+pub fn format_time(time: DateTime<Tz>, clock: &Clock) -> String {
+    match clock {
+        Clock::TwelveHour => {
+            let (pm, hour) = time.hour12();
+            format!(
+                "{}:{:0>2}{}",
+                hour,
+                time.minute(),
+                if pm { "pm" } else { "am" }
+            )
+        }
+        Clock::TwentyFourHour => time.format("%H:%M").to_string(),
+        Clock::Custom(format) => time.format(format).to_string(),
+    }
+}
+
+// 6:30pm-2:30am (+1), marking the number of days that the
+// end is past the start
+pub fn format_span(start: DateTime<Tz>, end: DateTime<Tz>, clock: &Clock) -> String {
+    let days = (end.date_naive() - start.date_naive()).num_days();
+
+    if days == 0 {
+        format!("{}-{}", format_time(start, clock), format_time(end, clock))
+    } else {
+        format!(
+            "{}-{} {}",
+            format_time(start, clock),
+            format_time(end, clock),
+            format!("({days:+})").yellow()
+        )
+    }
+}
+
+// 48h30m, -45m
+pub fn format_duration(duration: Duration) -> String {
+    let sign = if duration < Duration::zero() { "-" } else { "" };
+    let minutes = duration.num_minutes().abs();
+
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{sign}{m}m"),
+        (h, 0) => format!("{sign}{h}h"),
+        (h, m) => format!("{sign}{h}h{m}m"),
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{format_time, parse_date, today, Settings, WorkingHours};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use colored::*;
+
+// the hours of the night, which wrap around midnight
+pub const NIGHT_START: u32 = 22;
+pub const NIGHT_END: u32 = 7;
+
+const WIDTH: usize = 18;
+
+// the first instant of `date` in `timezone`, which is later than
+// midnight where the clocks go forward at midnight, as in Santiago
+fn start_of_day(timezone: Tz, date: NaiveDate) -> Option<DateTime<Utc>> {
+    (0..24 * 60)
+        .map(|minutes| date.and_time(NaiveTime::MIN) + Duration::minutes(minutes))
+        .find_map(|local| timezone.from_local_datetime(&local).earliest())
+        .map(|time| time.with_timezone(&Utc))
+}
+
+// the start of `date` in `timezone`, and the start of the next day
+pub fn day_bounds(timezone: Tz, date: NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    Some((
+        start_of_day(timezone, date)?,
+        start_of_day(timezone, date.succ_opt()?)?,
+    ))
+}
+
+// the start of each hour of `date` in `timezone`, which is not
+// always 24 of them when the clocks change
+pub fn hours_of_day(timezone: Tz, date: NaiveDate) -> Vec<DateTime<Utc>> {
+    let Some((start, end)) = day_bounds(timezone, date) else {
+        return vec![];
+    };
+
+    let mut hours = vec![];
+    let mut hour = start;
+
+    while hour < end {
+        hours.push(hour);
+        hour += Duration::hours(1);
+    }
+
+    hours
+}
+
+fn colour_cell(cell: String, local: DateTime<Tz>, hours: &WorkingHours) -> ColoredString {
+    let hour = local.hour();
+
+    if hours.covers(
+        local.naive_local(),
+        (local + Duration::hours(1)).naive_local(),
+    ) {
+        cell.green()
+    } else if !(NIGHT_END..NIGHT_START).contains(&hour) {
+        cell.blue().dimmed()
+    } else {
+        cell.normal()
+    }
+}
+
+/*
+ * pt et bst ist           - the hours of today in pt, side by side
+ * pt et bst ist next tue  - the hours of another day
+ *
+ * each timezone's working hours are green, the night is dimmed, and the current
+ * hour is marked
+ */
+pub fn grid(args: Vec<String>, settings: &Settings) {
+    let zones_end = args
+        .iter()
+        .take_while(|arg| settings.config.lookup_timezone(arg).is_some())
+        .count();
+
+    if zones_end == 0 {
+        println!(
+            "\n[{}] expected at least one timezone.\n\n\tUsage: tz grid timezone... [date]\n\tExample: tz grid pt et bst ist tomorrow\n",
+            "ERROR".red()
+        );
+        return;
+    }
+
+    let names = &args[..zones_end];
+    let timezones: Vec<Tz> = names
+        .iter()
+        .filter_map(|name| settings.config.lookup_timezone(name))
+        .collect();
+
+    let maybe_date = if args.len() > zones_end {
+        Some(args[zones_end..].join(" "))
+    } else {
+        None
+    };
+
+    let date = match parse_date(maybe_date.clone(), settings.locale, settings.order, today()) {
+        Some(date) => date,
+        None => {
+            println!(
+                "\n[{}] could not parse date {:?}\n",
+                "ERROR".red(),
+                maybe_date.unwrap_or_default()
+            );
+            return;
+        }
+    };
+
+    let hours: Vec<WorkingHours> = names
+        .iter()
+        .map(|name| settings.config.working_hours(name, date))
+        .collect();
+    let now = Utc::now();
+
+    println!("\n");
+    print!("  ");
+    for name in names {
+        print!("{:<WIDTH$}", name.bold());
+    }
+    println!();
+
+    let mut previous_dates: Vec<Option<NaiveDate>> = vec![None; timezones.len()];
+
+    for hour in hours_of_day(timezones[0], date) {
+        let current = hour <= now && now < hour + Duration::hours(1);
+        print!(
+            "{}",
+            if current {
+                "▶ ".yellow()
+            } else {
+                "  ".normal()
+            }
+        );
+
+        for (i, timezone) in timezones.iter().enumerate() {
+            let local = hour.with_timezone(timezone);
+
+            // the date is shown on the first row and wherever it changes
+            let cell = if previous_dates[i] != Some(local.date_naive()) {
+                previous_dates[i] = Some(local.date_naive());
+                format!(
+                    "{:<8}{}",
+                    format_time(local, &settings.config.clock),
+                    local.format("%a %-d")
+                )
+            } else {
+                format_time(local, &settings.config.clock)
+            };
+
+            let cell = colour_cell(format!("{cell:<WIDTH$}"), local, &hours[i]);
+
+            if current {
+                print!("{}", cell.bold());
+            } else {
+                print!("{cell}");
+            }
+        }
+        println!();
+    }
+    println!("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{date, zone};
+
+    #[test]
+    fn days_when_the_clocks_change() {
+        let london = zone("Europe/London");
+
+        assert_eq!(hours_of_day(london, date(2025, 3, 29)).len(), 24);
+        assert_eq!(hours_of_day(london, date(2025, 3, 30)).len(), 23);
+        assert_eq!(hours_of_day(london, date(2025, 10, 26)).len(), 25);
+    }
+
+    #[test]
+    fn days_without_a_midnight_start_at_the_first_time_there_is() {
+        let santiago = zone("America/Santiago");
+        let hours = hours_of_day(santiago, date(2025, 9, 7));
+
+        assert_eq!(hours.len(), 23);
+        assert_eq!(
+            hours[0].with_timezone(&santiago).naive_local(),
+            date(2025, 9, 7).and_hms_opt(1, 0, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,473 @@
+use chrono::Weekday;
+
+// the words that the date parser understands in a given
+// language. English is always understood alongside whichever
+// locale is selected, so that `20 feb` works everywhere.
+pub struct Locale {
+    pub months: &'static phf::Map<&'static str, u32>,
+    pub weekdays: &'static phf::Map<&'static str, Weekday>,
+    pub days: &'static phf::Map<&'static str, i64>, // offset from today
+    pub fillers: &'static [&'static str],           // "5 de mayo"
+    pub ordinals: &'static [&'static str],          // "1er", "3."
+}
+
+impl Locale {
+    // accepts a language code on its own, or as the start of a
+    // POSIX locale such as fr_FR.UTF-8
+    pub fn from_code(code: &str) -> Option<&'static Locale> {
+        let language = code
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        LOCALES.get(language.as_str()).copied()
+    }
+
+    pub fn from_env() -> &'static Locale {
+        Locale::from_code(&env_locale()).unwrap_or(&ENGLISH)
+    }
+
+    pub fn month(&self, word: &str) -> Option<u32> {
+        self.months
+            .get(word)
+            .or_else(|| ENGLISH.months.get(word))
+            .copied()
+    }
+
+    pub fn weekday(&self, word: &str) -> Option<Weekday> {
+        self.weekdays
+            .get(word)
+            .or_else(|| ENGLISH.weekdays.get(word))
+            .copied()
+    }
+
+    pub fn day_offset(&self, phrase: &str) -> Option<i64> {
+        self.days
+            .get(phrase)
+            .or_else(|| ENGLISH.days.get(phrase))
+            .copied()
+    }
+
+    pub fn is_filler(&self, word: &str) -> bool {
+        self.fillers.contains(&word) || ENGLISH.fillers.contains(&word)
+    }
+
+    // 1st, 1er, 3., 20th -> 1, 1, 3, 20
+    pub fn strip_ordinal<'a>(&self, token: &'a str) -> &'a str {
+        for suffix in self.ordinals.iter().chain(ENGLISH.ordinals) {
+            if let Some(n) = token.strip_suffix(suffix) {
+                if !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()) {
+                    return n;
+                }
+            }
+        }
+
+        token
+    }
+}
+
+// the first of the usual locale variables that is set
+pub fn env_locale() -> String {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+}
+
+pub static LOCALES: phf::Map<&'static str, &'static Locale> = phf::phf_map! {
+    "en" => &ENGLISH,
+    "fr" => &FRENCH,
+    "de" => &GERMAN,
+    "es" => &SPANISH,
+    "pt" => &PORTUGUESE,
+    "it" => &ITALIAN,
+    "nl" => &DUTCH,
+};
+
+pub static ENGLISH: Locale = Locale {
+    months: &phf::phf_map! {
+        "jan" => 1,
+        "january" => 1,
+        "feb" => 2,
+        "february" => 2,
+        "mar" => 3,
+        "march" => 3,
+        "apr" => 4,
+        "april" => 4,
+        "may" => 5,
+        "jun" => 6,
+        "june" => 6,
+        "jul" => 7,
+        "july" => 7,
+        "aug" => 8,
+        "august" => 8,
+        "sep" => 9,
+        "sept" => 9,
+        "september" => 9,
+        "oct" => 10,
+        "october" => 10,
+        "nov" => 11,
+        "november" => 11,
+        "dec" => 12,
+        "december" => 12,
+        "1" => 1,
+        "01" => 1,
+        "2" => 2,
+        "02" => 2,
+        "3" => 3,
+        "03" => 3,
+        "4" => 4,
+        "04" => 4,
+        "5" => 5,
+        "05" => 5,
+        "6" => 6,
+        "06" => 6,
+        "7" => 7,
+        "07" => 7,
+        "8" => 8,
+        "08" => 8,
+        "9" => 9,
+        "09" => 9,
+        "10" => 10,
+        "11" => 11,
+        "12" => 12,
+    },
+    weekdays: &phf::phf_map! {
+        "mon" => Weekday::Mon,
+        "monday" => Weekday::Mon,
+        "tue" => Weekday::Tue,
+        "tues" => Weekday::Tue,
+        "tuesday" => Weekday::Tue,
+        "wed" => Weekday::Wed,
+        "wednesday" => Weekday::Wed,
+        "thu" => Weekday::Thu,
+        "thur" => Weekday::Thu,
+        "thurs" => Weekday::Thu,
+        "thursday" => Weekday::Thu,
+        "fri" => Weekday::Fri,
+        "friday" => Weekday::Fri,
+        "sat" => Weekday::Sat,
+        "saturday" => Weekday::Sat,
+        "sun" => Weekday::Sun,
+        "sunday" => Weekday::Sun,
+    },
+    days: &phf::phf_map! {
+        "today" => 0,
+        "tomorrow" => 1,
+        "yesterday" => -1,
+        "day after tomorrow" => 2,
+        "day before yesterday" => -2,
+    },
+    fillers: &["the", "of"],
+    ordinals: &["st", "nd", "rd", "th"],
+};
+
+pub static FRENCH: Locale = Locale {
+    months: &phf::phf_map! {
+        "janvier" => 1,
+        "janv" => 1,
+        "février" => 2,
+        "fevrier" => 2,
+        "févr" => 2,
+        "fevr" => 2,
+        "mars" => 3,
+        "avril" => 4,
+        "avr" => 4,
+        "mai" => 5,
+        "juin" => 6,
+        "juillet" => 7,
+        "juil" => 7,
+        "août" => 8,
+        "aout" => 8,
+        "septembre" => 9,
+        "octobre" => 10,
+        "novembre" => 11,
+        "décembre" => 12,
+        "decembre" => 12,
+        "déc" => 12,
+    },
+    weekdays: &phf::phf_map! {
+        "lundi" => Weekday::Mon,
+        "lun" => Weekday::Mon,
+        "mardi" => Weekday::Tue,
+        "mar" => Weekday::Tue,
+        "mercredi" => Weekday::Wed,
+        "mer" => Weekday::Wed,
+        "jeudi" => Weekday::Thu,
+        "jeu" => Weekday::Thu,
+        "vendredi" => Weekday::Fri,
+        "ven" => Weekday::Fri,
+        "samedi" => Weekday::Sat,
+        "sam" => Weekday::Sat,
+        "dimanche" => Weekday::Sun,
+        "dim" => Weekday::Sun,
+    },
+    days: &phf::phf_map! {
+        "aujourd'hui" => 0,
+        "aujourdhui" => 0,
+        "demain" => 1,
+        "hier" => -1,
+        "après-demain" => 2,
+        "apres-demain" => 2,
+        "avant-hier" => -2,
+    },
+    fillers: &["le", "du"],
+    ordinals: &["er", "ère", "ème", "eme", "e"],
+};
+
+pub static GERMAN: Locale = Locale {
+    months: &phf::phf_map! {
+        "januar" => 1,
+        "jänner" => 1,
+        "februar" => 2,
+        "märz" => 3,
+        "maerz" => 3,
+        "marz" => 3,
+        "mär" => 3,
+        "mai" => 5,
+        "juni" => 6,
+        "juli" => 7,
+        "oktober" => 10,
+        "okt" => 10,
+        "dezember" => 12,
+        "dez" => 12,
+    },
+    weekdays: &phf::phf_map! {
+        "montag" => Weekday::Mon,
+        "mo" => Weekday::Mon,
+        "dienstag" => Weekday::Tue,
+        "di" => Weekday::Tue,
+        "mittwoch" => Weekday::Wed,
+        "mi" => Weekday::Wed,
+        "donnerstag" => Weekday::Thu,
+        "do" => Weekday::Thu,
+        "freitag" => Weekday::Fri,
+        "fr" => Weekday::Fri,
+        "samstag" => Weekday::Sat,
+        "sonnabend" => Weekday::Sat,
+        "sa" => Weekday::Sat,
+        "sonntag" => Weekday::Sun,
+        "so" => Weekday::Sun,
+    },
+    days: &phf::phf_map! {
+        "heute" => 0,
+        "morgen" => 1,
+        "gestern" => -1,
+        "übermorgen" => 2,
+        "uebermorgen" => 2,
+        "vorgestern" => -2,
+    },
+    fillers: &["den", "der", "am"],
+    ordinals: &["."],
+};
+
+pub static SPANISH: Locale = Locale {
+    months: &phf::phf_map! {
+        "enero" => 1,
+        "ene" => 1,
+        "febrero" => 2,
+        "marzo" => 3,
+        "abril" => 4,
+        "abr" => 4,
+        "mayo" => 5,
+        "junio" => 6,
+        "julio" => 7,
+        "agosto" => 8,
+        "ago" => 8,
+        "septiembre" => 9,
+        "setiembre" => 9,
+        "octubre" => 10,
+        "noviembre" => 11,
+        "diciembre" => 12,
+        "dic" => 12,
+    },
+    weekdays: &phf::phf_map! {
+        "lunes" => Weekday::Mon,
+        "lun" => Weekday::Mon,
+        "martes" => Weekday::Tue,
+        "miércoles" => Weekday::Wed,
+        "miercoles" => Weekday::Wed,
+        "mié" => Weekday::Wed,
+        "mie" => Weekday::Wed,
+        "jueves" => Weekday::Thu,
+        "jue" => Weekday::Thu,
+        "viernes" => Weekday::Fri,
+        "vie" => Weekday::Fri,
+        "sábado" => Weekday::Sat,
+        "sabado" => Weekday::Sat,
+        "sáb" => Weekday::Sat,
+        "sab" => Weekday::Sat,
+        "domingo" => Weekday::Sun,
+        "dom" => Weekday::Sun,
+    },
+    days: &phf::phf_map! {
+        "hoy" => 0,
+        "mañana" => 1,
+        "manana" => 1,
+        "ayer" => -1,
+        "pasado mañana" => 2,
+        "pasado manana" => 2,
+        "anteayer" => -2,
+        "antes de ayer" => -2,
+    },
+    fillers: &["de", "del", "el"],
+    ordinals: &["º", "ª"],
+};
+
+pub static PORTUGUESE: Locale = Locale {
+    months: &phf::phf_map! {
+        "janeiro" => 1,
+        "fevereiro" => 2,
+        "fev" => 2,
+        "março" => 3,
+        "marco" => 3,
+        "abril" => 4,
+        "abr" => 4,
+        "maio" => 5,
+        "mai" => 5,
+        "junho" => 6,
+        "julho" => 7,
+        "agosto" => 8,
+        "ago" => 8,
+        "setembro" => 9,
+        "set" => 9,
+        "outubro" => 10,
+        "out" => 10,
+        "novembro" => 11,
+        "dezembro" => 12,
+        "dez" => 12,
+    },
+    weekdays: &phf::phf_map! {
+        "segunda" => Weekday::Mon,
+        "segunda-feira" => Weekday::Mon,
+        "seg" => Weekday::Mon,
+        "terça" => Weekday::Tue,
+        "terca" => Weekday::Tue,
+        "terça-feira" => Weekday::Tue,
+        "terca-feira" => Weekday::Tue,
+        "ter" => Weekday::Tue,
+        "quarta" => Weekday::Wed,
+        "quarta-feira" => Weekday::Wed,
+        "qua" => Weekday::Wed,
+        "quinta" => Weekday::Thu,
+        "quinta-feira" => Weekday::Thu,
+        "qui" => Weekday::Thu,
+        "sexta" => Weekday::Fri,
+        "sexta-feira" => Weekday::Fri,
+        "sex" => Weekday::Fri,
+        "sábado" => Weekday::Sat,
+        "sabado" => Weekday::Sat,
+        "sáb" => Weekday::Sat,
+        "sab" => Weekday::Sat,
+        "domingo" => Weekday::Sun,
+        "dom" => Weekday::Sun,
+    },
+    days: &phf::phf_map! {
+        "hoje" => 0,
+        "amanhã" => 1,
+        "amanha" => 1,
+        "ontem" => -1,
+        "depois de amanhã" => 2,
+        "depois de amanha" => 2,
+        "anteontem" => -2,
+    },
+    fillers: &["de", "do", "da"],
+    ordinals: &["º", "ª"],
+};
+
+pub static ITALIAN: Locale = Locale {
+    months: &phf::phf_map! {
+        "gennaio" => 1,
+        "gen" => 1,
+        "febbraio" => 2,
+        "marzo" => 3,
+        "aprile" => 4,
+        "maggio" => 5,
+        "mag" => 5,
+        "giugno" => 6,
+        "giu" => 6,
+        "luglio" => 7,
+        "lug" => 7,
+        "agosto" => 8,
+        "ago" => 8,
+        "settembre" => 9,
+        "set" => 9,
+        "ottobre" => 10,
+        "ott" => 10,
+        "novembre" => 11,
+        "dicembre" => 12,
+        "dic" => 12,
+    },
+    weekdays: &phf::phf_map! {
+        "lunedì" => Weekday::Mon,
+        "lunedi" => Weekday::Mon,
+        "lun" => Weekday::Mon,
+        "martedì" => Weekday::Tue,
+        "martedi" => Weekday::Tue,
+        "mercoledì" => Weekday::Wed,
+        "mercoledi" => Weekday::Wed,
+        "mer" => Weekday::Wed,
+        "giovedì" => Weekday::Thu,
+        "giovedi" => Weekday::Thu,
+        "gio" => Weekday::Thu,
+        "venerdì" => Weekday::Fri,
+        "venerdi" => Weekday::Fri,
+        "ven" => Weekday::Fri,
+        "sabato" => Weekday::Sat,
+        "sab" => Weekday::Sat,
+        "domenica" => Weekday::Sun,
+        "dom" => Weekday::Sun,
+    },
+    days: &phf::phf_map! {
+        "oggi" => 0,
+        "domani" => 1,
+        "ieri" => -1,
+        "dopodomani" => 2,
+        "altroieri" => -2,
+        "l'altro ieri" => -2,
+    },
+    fillers: &["il", "di", "del"],
+    ordinals: &["º"],
+};
+
+pub static DUTCH: Locale = Locale {
+    months: &phf::phf_map! {
+        "januari" => 1,
+        "februari" => 2,
+        "maart" => 3,
+        "mrt" => 3,
+        "mei" => 5,
+        "juni" => 6,
+        "juli" => 7,
+        "augustus" => 8,
+        "oktober" => 10,
+        "okt" => 10,
+    },
+    weekdays: &phf::phf_map! {
+        "maandag" => Weekday::Mon,
+        "ma" => Weekday::Mon,
+        "dinsdag" => Weekday::Tue,
+        "di" => Weekday::Tue,
+        "woensdag" => Weekday::Wed,
+        "wo" => Weekday::Wed,
+        "donderdag" => Weekday::Thu,
+        "do" => Weekday::Thu,
+        "vrijdag" => Weekday::Fri,
+        "vr" => Weekday::Fri,
+        "zaterdag" => Weekday::Sat,
+        "za" => Weekday::Sat,
+        "zondag" => Weekday::Sun,
+        "zo" => Weekday::Sun,
+    },
+    days: &phf::phf_map! {
+        "vandaag" => 0,
+        "morgen" => 1,
+        "gisteren" => -1,
+        "overmorgen" => 2,
+        "eergisteren" => -2,
+    },
+    fillers: &["de", "van"],
+    ordinals: &["ste", "de", "e"],
+};
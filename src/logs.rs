@@ -0,0 +1,327 @@
+use crate::{local_timezone, stream_error, take_option, Settings};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use colored::*;
+use regex::{Captures, Regex};
+use std::io::{BufRead, Write};
+
+// 2024-03-10T14:30:00Z, 2024-03-10 14:30:00.123+01:00
+const ISO_8601: &str =
+    r"\b(\d{4}-\d{2}-\d{2})([T ])(\d{2}:\d{2}:\d{2})(\.\d+)?(Z|[+-]\d{2}:?\d{2})?";
+// Mar 10 14:30:00
+const SYSLOG: &str =
+    r"\b((?:Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec) [ \d]\d \d{2}:\d{2}:\d{2})\b";
+// [10/Oct/2000:13:55:36 -0700]
+const COMMON_LOG: &str = r"\[(\d{2}/\w{3}/\d{4}:\d{2}:\d{2}:\d{2} [+-]\d{4})\]";
+// 1710081000, or 1710081000123 in milliseconds
+const EPOCH: &str = r"\b(1\d{9})(\d{3})?\b";
+
+// the zones that log timestamps are read in and written out in
+struct Zones {
+    from: Tz,
+    to: Tz,
+}
+
+fn rewrite_iso_8601(captures: &Captures, zones: &Zones) -> Option<String> {
+    let fraction = captures.get(4).map(|m| m.as_str()).unwrap_or_default();
+    let naive = NaiveDateTime::parse_from_str(
+        &format!("{} {}{fraction}", &captures[1], &captures[3]),
+        "%Y-%m-%d %H:%M:%S%.f",
+    )
+    .ok()?;
+
+    let converted = match captures.get(5) {
+        Some(offset) => {
+            let offset = match offset.as_str() {
+                "Z" => FixedOffset::east_opt(0)?,
+                offset => DateTime::parse_from_str(
+                    &format!("2000-01-01 00:00 {offset}"),
+                    "%Y-%m-%d %H:%M %z",
+                )
+                .ok()?
+                .timezone(),
+            };
+            offset
+                .from_local_datetime(&naive)
+                .single()?
+                .with_timezone(&zones.to)
+        }
+        None => zones
+            .from
+            .from_local_datetime(&naive)
+            .earliest()?
+            .with_timezone(&zones.to),
+    };
+
+    // keep the separator and precision, and the offset if there was one
+    let precision = match fraction.len() {
+        0 => "",
+        4 => "%.3f",
+        7 => "%.6f",
+        10 => "%.9f",
+        _ => "%.f",
+    };
+    let offset = if captures.get(5).is_some() { "%:z" } else { "" };
+
+    Some(
+        converted
+            .format(&format!(
+                "%Y-%m-%d{}%H:%M:%S{precision}{offset}",
+                &captures[2]
+            ))
+            .to_string(),
+    )
+}
+
+// syslog leaves out the year, so it is taken to be this year
+fn rewrite_syslog(captures: &Captures, zones: &Zones) -> Option<String> {
+    let year = Utc::now().with_timezone(&zones.from).year();
+    let naive =
+        NaiveDateTime::parse_from_str(&format!("{year} {}", &captures[1]), "%Y %b %e %H:%M:%S")
+            .ok()?;
+
+    let converted = zones
+        .from
+        .from_local_datetime(&naive)
+        .earliest()?
+        .with_timezone(&zones.to);
+
+    Some(converted.format("%b %e %H:%M:%S").to_string())
+}
+
+fn rewrite_common_log(captures: &Captures, zones: &Zones) -> Option<String> {
+    let converted = DateTime::parse_from_str(&captures[1], "%d/%b/%Y:%H:%M:%S %z")
+        .ok()?
+        .with_timezone(&zones.to);
+
+    Some(converted.format("[%d/%b/%Y:%H:%M:%S %z]").to_string())
+}
+
+// an epoch has no timezone, so it is written out as ISO 8601
+fn rewrite_epoch(captures: &Captures, zones: &Zones) -> Option<String> {
+    let seconds: i64 = captures[1].parse().ok()?;
+    let milliseconds: u32 = match captures.get(2) {
+        Some(milliseconds) => milliseconds.as_str().parse().ok()?,
+        None => 0,
+    };
+
+    let converted = Utc
+        .timestamp_opt(seconds, milliseconds * 1_000_000)
+        .single()?
+        .with_timezone(&zones.to);
+
+    Some(match captures.get(2) {
+        Some(_) => converted.format("%Y-%m-%dT%H:%M:%S%.3f%:z").to_string(),
+        None => converted.format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+    })
+}
+
+type Rewrite = fn(&Captures, &Zones) -> Option<String>;
+
+// each kind of timestamp that is recognised, with epochs only when
+// asked for, as any other number of 10 or 13 digits looks the same
+fn rewrites(epoch: bool) -> Vec<(Regex, Rewrite)> {
+    let mut rewrites: Vec<(Regex, Rewrite)> = vec![
+        (Regex::new(ISO_8601).unwrap(), rewrite_iso_8601),
+        (Regex::new(SYSLOG).unwrap(), rewrite_syslog),
+        (Regex::new(COMMON_LOG).unwrap(), rewrite_common_log),
+    ];
+
+    if epoch {
+        rewrites.push((Regex::new(EPOCH).unwrap(), rewrite_epoch));
+    }
+
+    rewrites
+}
+
+// every recognised timestamp in `text` rewritten, leaving any
+// that cannot be converted as they are
+fn rewrite_all(text: &str, rewrites: &[(Regex, Rewrite)], zones: &Zones) -> String {
+    rewrites
+        .iter()
+        .fold(text.to_string(), |text, (regex, rewrite)| {
+            regex
+                .replace_all(&text, |captures: &Captures| {
+                    rewrite(captures, zones).unwrap_or(captures[0].to_string())
+                })
+                .to_string()
+        })
+}
+
+// a timestamp matched by --pattern, read with --format if it was
+// given, and otherwise as any of the recognised timestamps
+fn rewrite_custom(
+    text: &str,
+    format: &Option<String>,
+    rewrites: &[(Regex, Rewrite)],
+    zones: &Zones,
+) -> String {
+    let Some(format) = format else {
+        return rewrite_all(text, rewrites, zones);
+    };
+
+    let converted = match DateTime::parse_from_str(text, format) {
+        Ok(time) => Some(time.with_timezone(&zones.to)),
+        Err(_) => NaiveDateTime::parse_from_str(text, format)
+            .ok()
+            .and_then(|naive| zones.from.from_local_datetime(&naive).earliest())
+            .map(|time| time.with_timezone(&zones.to)),
+    };
+
+    match converted {
+        Some(time) => time.format(format).to_string(),
+        None => text.to_string(),
+    }
+}
+
+// local is the configured or detected local timezone
+fn lookup_zone(name: &str, settings: &Settings) -> Option<Tz> {
+    match name {
+        "local" => match &settings.config.local {
+            Some(local) => settings.config.lookup_timezone(local),
+            None => settings.config.lookup_timezone(&local_timezone()),
+        },
+        _ => settings.config.lookup_timezone(name),
+    }
+}
+
+/*
+ * --from utc          - the timezone of timestamps without an offset
+ * --to local          - the timezone to rewrite them in
+ * --pattern REGEX     - only rewrite what this matches, or its
+ *                       group named time
+ * --format '%d.%m.%Y %H:%M'
+ *                     - how the pattern's timestamps are written
+ * --epoch             - also rewrite epochs, eg. 1710081000
+ */
+pub fn logs(mut args: Vec<String>, settings: &Settings) {
+    let epoch = args.iter().any(|arg| arg == "--epoch");
+    args.retain(|arg| arg != "--epoch");
+
+    let from_name = take_option(&mut args, "--from").unwrap_or("utc".to_string());
+    let to_name = take_option(&mut args, "--to").unwrap_or("local".to_string());
+    let format = take_option(&mut args, "--format");
+
+    let pattern = match take_option(&mut args, "--pattern").map(|pattern| Regex::new(&pattern)) {
+        Some(Ok(pattern)) => Some(pattern),
+        Some(Err(error)) => {
+            return println!("\n[{}] cannot parse the pattern: {error}\n", "ERROR".red());
+        }
+        None => None,
+    };
+
+    let zones = match (
+        lookup_zone(&from_name, settings),
+        lookup_zone(&to_name, settings),
+    ) {
+        (Some(from), Some(to)) => Zones { from, to },
+        (None, _) => return println!("\n[{}] cannot parse origin {from_name}\n", "ERROR".red()),
+        (_, None) => return println!("\n[{}] cannot parse destination {to_name}\n", "ERROR".red()),
+    };
+
+    let rewrites = rewrites(epoch);
+    let mut stdout = std::io::stdout().lock();
+
+    for line in std::io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => return println!("\n[{}] {error}\n", "ERROR".red()),
+        };
+
+        let rewritten = match &pattern {
+            Some(pattern) => pattern
+                .replace_all(&line, |captures: &Captures| {
+                    let whole = captures.get(0).unwrap();
+                    let time = captures.name("time").unwrap_or(whole);
+                    let rewritten = rewrite_custom(time.as_str(), &format, &rewrites, &zones);
+
+                    // only the time group is replaced
+                    let start = time.start() - whole.start();
+                    let end = time.end() - whole.start();
+                    let whole = whole.as_str();
+                    format!("{}{rewritten}{}", &whole[..start], &whole[end..])
+                })
+                .to_string(),
+            None => rewrite_all(&line, &rewrites, &zones),
+        };
+
+        if let Err(error) = writeln!(stdout, "{rewritten}") {
+            return stream_error(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::zone;
+
+    fn rewritten(text: &str, epoch: bool) -> String {
+        let zones = Zones {
+            from: chrono_tz::UTC,
+            to: zone("America/New_York"),
+        };
+
+        rewrite_all(text, &rewrites(epoch), &zones)
+    }
+
+    #[test]
+    fn iso_8601() {
+        assert_eq!(
+            rewritten("2024-03-10T14:30:00Z INFO start", false),
+            "2024-03-10T10:30:00-04:00 INFO start"
+        );
+        assert_eq!(
+            rewritten("at 2024-01-10T14:30:00+01:00", false),
+            "at 2024-01-10T08:30:00-05:00"
+        );
+        assert_eq!(
+            rewritten("2024-03-10 14:30:00 INFO start", false),
+            "2024-03-10 10:30:00 INFO start"
+        );
+    }
+
+    #[test]
+    fn iso_8601_keeps_its_precision() {
+        assert_eq!(
+            rewritten("2024-03-10T14:30:00.123Z", false),
+            "2024-03-10T10:30:00.123-04:00"
+        );
+        assert_eq!(
+            rewritten("2024-03-10 14:30:00.123456", false),
+            "2024-03-10 10:30:00.123456"
+        );
+    }
+
+    #[test]
+    fn syslog() {
+        assert_eq!(
+            rewritten("Jul  4 14:30:00 host sshd[1]: ok", false),
+            "Jul  4 10:30:00 host sshd[1]: ok"
+        );
+    }
+
+    #[test]
+    fn common_log_format() {
+        assert_eq!(
+            rewritten(
+                r#"127.0.0.1 - - [10/Oct/2000:13:55:36 -0700] "GET /" 200 1234567890"#,
+                false
+            ),
+            r#"127.0.0.1 - - [10/Oct/2000:16:55:36 -0400] "GET /" 200 1234567890"#
+        );
+    }
+
+    #[test]
+    fn epochs_only_when_asked_for() {
+        assert_eq!(rewritten("bytes=1234567890", false), "bytes=1234567890");
+        assert_eq!(
+            rewritten("ts=1710081000", true),
+            "ts=2024-03-10T10:30:00-04:00"
+        );
+        assert_eq!(
+            rewritten("ts=1710081000123", true),
+            "ts=2024-03-10T10:30:00.123-04:00"
+        );
+    }
+}
@@ -1,7 +1,9 @@
-use chrono::{DateTime, TimeZone, Timelike};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone};
 use chrono_tz::Tz;
 use colored::*;
 use std::env;
+use std::ffi::OsString;
+use std::io::IsTerminal;
 
 mod parse;
 use parse::*;
@@ -9,270 +11,770 @@ use parse::*;
 mod types;
 use types::*;
 
+mod locale;
+use locale::*;
+
+mod format;
+use format::*;
+
+mod diff;
+mod grid;
+mod offset;
+mod plan;
+
+mod transitions;
+use transitions::*;
+
+mod config;
+use config::*;
+
+mod people;
+use people::*;
+
+mod repl;
+
+mod batch;
+
+mod filter;
+
+mod logs;
+
+mod completions;
+
+mod zones;
+
+mod cli;
+use cli::*;
+
+mod watch;
+
+mod tui;
+
+#[cfg(test)]
+mod testing;
+
 mod convert_timezones;
 use convert_timezones::*;
 
 /*
  * 1pm EST                          - assumes EST -> your timezone
  * 1pm EST BST                      - assumes 1pm EST on the current day
+ * 1pm EST BST IST                  - any number of destinations
  * 1pm EST BST tomorrow/yesterday/n - n being the nth day of the current month
- * 1pm EST BST 20 feb               - assumes feb of the current year
- * 1pm EST BST 20 feb 2020          - fully specified
+ * 1pm EST BST next friday          - see parse_day for the accepted expressions
+ * 1pm EST BST 20th feb             - assumes feb of the current year
+ * 1pm EST BST feb 20 2020          - fully specified, see parse_date
+ * 9am-5pm EST IST                  - a span of time, see parse_time_range
+ * 2pm EST + 45m BST                - shifted by a duration, see parse_duration
+ * now + 90m BST, in 6 hours AET    - from the current instant, every timezone
+ *                                    given is a destination
  */
 
-fn convert(
+// when a time shifted by a duration is past the dates chrono can hold
+const TOO_FAR: &str = "the durations move the time too far away to convert.";
+
+// a conversion as it was given, split into its parts
+struct Conversion {
     time: String,
-    origin: String,
-    destination: Option<String>,
-    day: Option<String>,
-    month: Option<String>,
-    year: Option<String>,
-) {
-    let maybe_time = parse_time(time.clone().to_lowercase());
-
-    if maybe_time == None {
-        println!(
-            "\n[{}] cannot parse the time \"{}\". Please format the time as one of the following:\n\n\tSimpleAmPm    - eg. 1am, 10pm, etc.\n\tFullAmPm      - eg. 12:24am, 6:30pm, etc.\n\tMilitaryColon - eg. 07:00, 13:52, etc.\n\tMilitary      - eg. 0900, 1634, etc.\n\n[{}] this software is pretty good at working out which format you are using, make sure that if you specified am/pm that you are not using 24 hours and that you don't go over 59 minutes.",
-            "ERROR".red(),
-            time,
-            "HINT".cyan()
-        );
-        return;
-    }
+    origin: Option<String>,
+    destinations: Vec<String>,
+    adjustments: Vec<String>,
+    date: Option<String>,
+}
 
-    let (hours, minutes) = maybe_time.unwrap();
+// what was given, and each destination it converts to, or else
+// the reason that it cannot be converted
+fn convert(
+    conversion: Conversion,
+    settings: &Settings,
+) -> Result<(String, Vec<Converted>), String> {
+    let Conversion {
+        time,
+        origin,
+        destinations,
+        adjustments,
+        date,
+    } = conversion;
+
+    let adjustment: Duration = adjustments
+        .iter()
+        .try_fold(Duration::zero(), |total, adjustment| {
+            total.checked_add(&parse_adjustment(adjustment.clone())?)
+        })
+        .ok_or(format!(
+            "cannot parse the durations {:?}, expected eg. + 90m, - 1h30m, + PT2H.",
+            adjustments.join(" ")
+        ))?;
+
+    // the current instant does not depend on a timezone
+    if let Some(from_now) = parse_relative_time(time.clone()) {
+        if let Some(date) = date {
+            return Err(format!(
+                "cannot give a date ({date}) for a time relative to now."
+            ));
+        }
 
-    let (maybe_origin_timezone, maybe_destination_timezone) =
-        parse_timezone(origin.clone(), destination.clone());
+        let instant = chrono::Utc::now()
+            .checked_add_signed(from_now)
+            .and_then(|instant| instant.checked_add_signed(adjustment))
+            .ok_or(TOO_FAR.to_string())?;
 
-    if maybe_origin_timezone == None {
-        println!("\n[{}] cannot parse origin {}", "ERROR".red(), origin);
-        return;
+        return convert_instant(time, instant, destinations, adjustments, settings);
     }
 
-    if maybe_destination_timezone == None {
-        println!(
-            "\n[{}] cannot parse destination {:?} and cannot get local timezone.",
-            "ERROR".red(),
+    let origin = origin.unwrap_or_default();
+
+    let ((hours, minutes), end) = match parse_time(time.clone().to_lowercase()) {
+        Some(start) => Some((start, None)),
+        None => {
+            parse_time_range(time.clone().to_lowercase()).map(|(start, end)| (start, Some(end)))
+        }
+    }
+    .ok_or(format!(
+        "cannot parse the time \"{}\".\nPlease format the time as one of the following:\n\n\tSimpleAmPm    - eg. 1am, 10pm, etc.\n\tFullAmPm      - eg. 12:24am, 6:30pm, etc.\n\tMilitaryColon - eg. 07:00, 13:52, etc.\n\tMilitary      - eg. 0900, 1634, etc.\n\nor as a range of two of these, eg. 9am-5pm, 9-5pm, 13:00-14:30, 9am to 5pm.\n\n[{}] this software is pretty good at working out which format you are using, make sure that if you specified am/pm that you are not using 24 hours and that you don't go over 59 minutes.",
+        time,
+        "HINT".cyan()
+    ))?;
+
+    let naive_date =
+        parse_date(date.clone(), settings.locale, settings.order, today()).ok_or(format!(
+            "could not parse date {:?}",
+            date.clone().unwrap_or_default()
+        ))?;
+
+    let mut converted: Vec<Converted> = vec![];
+
+    for destination in default_destinations(destinations, settings) {
+        let (maybe_origin_timezone, maybe_destination_timezone) =
+            parse_timezone(origin.clone(), destination.clone(), settings);
+
+        let origin_timezone =
+            maybe_origin_timezone.ok_or(format!("cannot parse origin {origin}"))?;
+        let destination_timezone = maybe_destination_timezone.ok_or(format!(
+            "cannot parse destination {:?} and cannot get local timezone.",
             destination
-        );
-        return;
+        ))?;
+
+        let origin_time = local_instant(origin_timezone, &origin, naive_date, (hours, minutes))?
+            .checked_add_signed(adjustment)
+            .ok_or(TOO_FAR.to_string())?;
+        let destination_time = origin_time.with_timezone(&destination_timezone);
+
+        let mut destination_end = None;
+
+        let formatted = match end {
+            Some((end_hours, end_minutes)) => {
+                // a range that ends earlier in the day than it starts, such
+                // as 10pm-2am, ends on the following day
+                let end_date = if (end_hours, end_minutes) < (hours, minutes) {
+                    naive_date.succ_opt().ok_or(TOO_FAR.to_string())?
+                } else {
+                    naive_date
+                };
+
+                let origin_end =
+                    local_instant(origin_timezone, &origin, end_date, (end_hours, end_minutes))?
+                        .checked_add_signed(adjustment)
+                        .ok_or(TOO_FAR.to_string())?;
+
+                destination_end = Some(origin_end.with_timezone(&destination_timezone));
+
+                format_span(
+                    destination_time,
+                    destination_end.unwrap(),
+                    &settings.config.clock,
+                )
+            }
+            None => format_time(destination_time, &settings.config.clock),
+        };
+
+        converted.push(Converted {
+            note: working_hours_note(settings, &destination, destination_time, destination_end),
+            destination,
+            time: formatted,
+            day: destination_time.date_naive(),
+        });
     }
 
-    let (origin_timezone, destination_timezone) = (
-        maybe_origin_timezone.unwrap(),
-        maybe_destination_timezone.unwrap(),
-    );
+    let given = [vec![time, origin], adjustments, date.into_iter().collect()].concat();
 
-    let maybe_day = parse_day(day.clone());
+    Ok((given.join(" "), converted))
+}
+
+// the instant of a time on a date, the earlier of the two when the
+// clocks go back, and an error when the clocks go forward past it
+fn local_instant(
+    timezone: Tz,
+    name: &str,
+    date: NaiveDate,
+    (hours, minutes): (u32, u32),
+) -> Result<DateTime<Tz>, String> {
+    date.and_hms_opt(hours, minutes, 0)
+        .and_then(|naive| timezone.from_local_datetime(&naive).earliest())
+        .ok_or(format!(
+            "{hours:02}:{minutes:02} does not exist in {name} on {date}, the clocks go forward past it."
+        ))
+}
 
-    if maybe_day == None {
-        println!("[{}] could not parse day {:?}", "ERROR".red(), day);
-        return;
+fn convert_instant(
+    time: String,
+    instant: DateTime<chrono::Utc>,
+    destinations: Vec<String>,
+    adjustments: Vec<String>,
+    settings: &Settings,
+) -> Result<(String, Vec<Converted>), String> {
+    let mut converted: Vec<Converted> = vec![];
+
+    for destination in default_destinations(destinations, settings) {
+        let (_, maybe_destination_timezone) =
+            parse_timezone("utc".to_string(), destination.clone(), settings);
+
+        let destination_timezone = maybe_destination_timezone.ok_or(format!(
+            "cannot parse destination {:?} and cannot get local timezone.",
+            destination
+        ))?;
+
+        let destination_time = instant.with_timezone(&destination_timezone);
+
+        converted.push(Converted {
+            note: working_hours_note(settings, &destination, destination_time, None),
+            destination,
+            time: format_time(destination_time, &settings.config.clock),
+            day: destination_time.date_naive(),
+        });
     }
 
-    let day_n = maybe_day.unwrap();
+    let given = [vec![time], adjustments].concat();
+
+    Ok((given.join(" "), converted))
+}
 
-    let maybe_month = parse_month(month.clone());
+// the destinations given, or else those in the config file, or
+// else the local timezone
+fn default_destinations(destinations: Vec<String>, settings: &Settings) -> Vec<Option<String>> {
+    let destinations = if destinations.is_empty() {
+        settings.config.destinations.clone()
+    } else {
+        destinations
+    };
 
-    if maybe_month == None {
-        println!("[{}] could not parse month {:?}", "ERROR".red(), month);
-        return;
+    if destinations.is_empty() {
+        vec![None]
+    } else {
+        destinations.into_iter().map(Some).collect()
     }
+}
 
-    let month_n = maybe_month.unwrap();
+// one destination of a conversion, as it is printed
+struct Converted {
+    destination: Option<String>,
+    time: String,
+    note: Option<String>,
+    day: NaiveDate, // the date at the destination
+}
 
-    let maybe_year = parse_year(year.clone());
+// 7:00am bst — before working hours, only where working hours
+// have been configured for the destination
+fn working_hours_note(
+    settings: &Settings,
+    destination: &Option<String>,
+    start: DateTime<Tz>,
+    end: Option<DateTime<Tz>>,
+) -> Option<String> {
+    let name = destination.clone().unwrap_or_default();
+    let hours = settings
+        .config
+        .configured_working_hours(&name, start.date_naive())?;
+
+    Some(
+        hours
+            .describe(start.naive_local(), end.unwrap_or(start).naive_local())
+            .to_string(),
+    )
+}
+
+// one line for each destination, eg. 1pm et is 6:00pm bst
+fn output_lines(given: &str, converted: &[Converted]) -> Vec<String> {
+    converted
+        .iter()
+        .map(|converted| {
+            let destination = converted.destination.as_deref().unwrap_or("local time");
+            let time = &converted.time;
+
+            match &converted.note {
+                Some(note) => format!("{given} is {time} {destination} — {}", note.cyan()),
+                None => format!("{given} is {time} {destination}"),
+            }
+        })
+        .collect()
+}
 
-    if maybe_year == None {
-        println!("[{}] could not parse year {:?}", "ERROR".red(), year);
-        return;
+// what is printed for a conversion
+fn output_text(given: &str, converted: &[Converted], settings: &Settings) -> String {
+    // just the times, one per destination, for scripts and prompts
+    if settings.quiet {
+        return converted
+            .iter()
+            .map(|converted| format!("{}\n", converted.time))
+            .collect();
     }
 
-    let year_n = maybe_year.unwrap();
+    format!("\n\n{}\n\n\n", output_lines(given, converted).join("\n"))
+}
 
-    let origin_time = origin_timezone
-        .with_ymd_and_hms(year_n, month_n, day_n, hours, minutes, 0)
-        .unwrap();
-    let destination_time = origin_time.with_timezone(&destination_timezone);
+fn output(given: String, converted: Vec<Converted>, settings: &Settings) {
+    print!("{}", output_text(&given, &converted, settings));
+}
 
-    output(
-        time,
-        origin,
-        destination,
-        day,
-        month,
-        year,
-        origin_time,
-        destination_time,
+// -q/--quiet and --no-warnings, as (quiet, warnings), taken only from
+// before the command word so that the arguments of a command reach it
+// as they are
+fn take_quiet_flags(args: &mut Vec<String>) -> (bool, bool) {
+    let leading = args
+        .iter()
+        .take_while(|arg| ["-q", "--quiet", "--no-warnings"].contains(&arg.as_str()))
+        .count();
+    let flags: Vec<String> = args.drain(..leading).collect();
+
+    let quiet = flags.iter().any(|flag| flag == "-q" || flag == "--quiet");
+    (
+        quiet,
+        !quiet && !flags.iter().any(|flag| flag == "--no-warnings"),
     )
 }
 
-fn output(
-    time: String,
-    origin: String,
-    destination: Option<String>,
-    day: Option<String>,
-    month: Option<String>,
-    year: Option<String>,
-    origin_time: DateTime<Tz>,
-    destination_time: DateTime<Tz>,
-) {
-    println!("\n");
-    match (time, origin, destination, day, month, year) {
-        (time, origin, Some(destination), Some(day), Some(month), Some(year)) => {
-            let (pm, hour) = destination_time.hour12();
-            println!(
-                "{time} {origin} {day} {month} {year} is {}:{:0>2}{} {destination}",
-                hour,
-                destination_time.minute(),
-                if pm { "pm" } else { "am" }
-            );
-        }
-        (time, origin, Some(destination), Some(day), Some(month), None) => {
-            let (pm, hour) = destination_time.hour12();
-            println!(
-                "{time} {origin} {day} {month} is {}:{:0>2}{} {destination}",
-                hour,
-                destination_time.minute(),
-                if pm { "pm" } else { "am" }
-            );
-        }
-        (time, origin, Some(destination), Some(day), None, None) => {
-            let (pm, hour) = destination_time.hour12();
-            println!(
-                "{time} {origin} {day} is {}:{:0>2}{} {destination}",
-                hour,
-                destination_time.minute(),
-                if pm { "pm" } else { "am" }
-            );
+// ends a stream whose output cannot be written, quietly when the
+// reader has gone, eg. | head -1, and otherwise on stderr, as stdout
+// is what failed
+fn stream_error(error: std::io::Error) {
+    if error.kind() != std::io::ErrorKind::BrokenPipe {
+        eprintln!("\n[{}] cannot write the output: {error}\n", "ERROR".red());
+    }
+}
+
+// removes `--name value` or `--name=value` from the arguments,
+// returning the value
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let prefix = format!("{name}=");
+
+    if let Some(i) = args.iter().position(|arg| arg.starts_with(&prefix)) {
+        return Some(args.remove(i)[prefix.len()..].to_string());
+    }
+
+    let i = args.iter().position(|arg| arg == name)?;
+    args.remove(i);
+
+    if i < args.len() {
+        Some(args.remove(i))
+    } else {
+        None
+    }
+}
+
+// --color, or else the config file, or else $NO_COLOR and
+// $CLICOLOR_FORCE, otherwise only when writing to a terminal, as
+// all output, errors included, goes to stdout. `var` reads the
+// environment.
+fn use_color(
+    flag: Option<&str>,
+    config: Option<bool>,
+    var: impl Fn(&str) -> Option<OsString>,
+    terminal: bool,
+) -> Result<bool, String> {
+    let choice = match flag {
+        Some("always") => Some(true),
+        Some("never") => Some(false),
+        Some("auto") => None,
+        None => config,
+        Some(other) => {
+            return Err(format!(
+                "unknown color \"{other}\", expected auto, always, or never."
+            ))
         }
-        (time, origin, Some(destination), None, None, None) => {
-            let (pm, hour) = destination_time.hour12();
-            println!(
-                "{time} {origin} is {}:{:0>2}{} {destination}",
-                hour,
-                destination_time.minute(),
-                if pm { "pm" } else { "am" }
-            );
+    };
+
+    // set, and for CLICOLOR_FORCE and CLICOLOR, other than 0
+    let set = |name| var(name).is_some_and(|value| !value.is_empty());
+    let zero = |name| var(name).is_some_and(|value| value == "0");
+
+    Ok(choice.unwrap_or_else(|| {
+        if set("NO_COLOR") {
+            false
+        } else if set("CLICOLOR_FORCE") && !zero("CLICOLOR_FORCE") {
+            true
+        } else {
+            !zero("CLICOLOR") && terminal
         }
-        (time, origin, None, None, None, None) => {
-            let (pm, hour) = destination_time.hour12();
-            println!(
-                "{time} {origin} is {}:{:0>2}{} local time",
-                hour,
-                destination_time.minute(),
-                if pm { "pm" } else { "am" }
-            );
+    }))
+}
+
+// removes `--name date` from the arguments, where the date can
+// take up several arguments, eg. `--date next tue pt et`
+fn take_date_option(args: &mut Vec<String>, name: &str, settings: &Settings) -> Option<String> {
+    let start = args.iter().position(|arg| arg == name)?;
+    let mut end = start + 1;
+
+    while end < args.len() && !args[end].starts_with("--") {
+        let date = args[start + 1..end].join(" ");
+
+        // stop at the first timezone after a complete date
+        if end > start + 1
+            && parse_date(Some(date), settings.locale, settings.order, today()).is_some()
+            && settings.config.lookup_timezone(&args[end]).is_some()
+        {
+            break;
         }
 
-        _ => {
-            println!("{} is {}", origin_time, destination_time.to_string().cyan())
+        end += 1;
+    }
+
+    let taken: Vec<String> = args.drain(start..end).skip(1).collect();
+
+    if taken.is_empty() {
+        None
+    } else {
+        Some(taken.join(" "))
+    }
+}
+
+// removes durations such as `+ 45m` or `-1h` from the arguments,
+// returning them
+fn take_adjustments(args: &mut Vec<String>) -> Vec<String> {
+    let mut adjustments = vec![];
+    let mut i = 0;
+
+    while i < args.len() {
+        if args[i] == "+" || args[i] == "-" {
+            // + 45m, + 3 hours
+            let maybe_n = (1..(args.len() - i).min(4))
+                .rev()
+                .find(|&n| parse_duration(args[i + 1..i + 1 + n].join(" ")).is_some());
+
+            if let Some(n) = maybe_n {
+                adjustments.push(args.drain(i..i + 1 + n).collect::<Vec<String>>().join(" "));
+                continue;
+            }
+        } else if parse_adjustment(args[i].clone()).is_some() {
+            // +45m
+            adjustments.push(args.remove(i));
+            continue;
         }
-    };
 
-    println!("\n")
+        i += 1;
+    }
+
+    adjustments
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect::<Vec<String>>()[1..].to_vec();
+    let mut args: Vec<String> = env::args().collect::<Vec<String>>()[1..].to_vec();
+
+    // decided before anything is printed, and again once the config
+    // file, which can also choose, has been read
+    let color = take_option(&mut args, "--color");
+    let terminal = std::io::stdout().is_terminal();
+    match use_color(color.as_deref(), None, |name| env::var_os(name), terminal) {
+        Ok(color) => colored::control::set_override(color),
+        Err(error) => return println!("\n[{}] {error}\n", "ERROR".red()),
+    }
 
-    if args.len() == 0 {
-        println!(
-            "
-Timezone conversion on the command line.
+    let locale = match take_option(&mut args, "--lang") {
+        Some(lang) => match Locale::from_code(&lang) {
+            Some(locale) => locale,
+            None => {
+                println!(
+                    "\n[{}] unknown language \"{lang}\", expected one of: {}.\n",
+                    "ERROR".red(),
+                    LOCALES.keys().copied().collect::<Vec<&str>>().join(", ")
+                );
+                return;
+            }
+        },
+        None => Locale::from_env(),
+    };
 
-\tUsage: time origin_timezone destination_timezone day month year
-\tExample: 1pm et bst tomorrow
-\t  ↳ display what 1pm eastern time is in British summer time tomorrow.
+    let order = match take_option(&mut args, "--date-order") {
+        Some(order) => match DateOrder::parse(&order) {
+            Some(order) => order,
+            None => {
+                println!(
+                    "\n[{}] unknown date order \"{order}\", expected dmy or mdy.\n",
+                    "ERROR".red()
+                );
+                return;
+            }
+        },
+        None => DateOrder::from_env(),
+    };
 
-time should be in one of the following formats:
+    let config = match Config::load(take_option(&mut args, "--config")) {
+        Ok(config) => config,
+        Err(error) => return println!("\n[{}] {error}\n", "ERROR".red()),
+    };
 
-\tSimpleAmPm    - eg. 1am, 10pm, etc.
-\tFullAmPm      - eg. 12:24am, 6:30pm, etc.
-\tMilitaryColon - eg. 07:00, 13:52, etc.
-\tMilitary      - eg. 0900, 1634, etc.
+    if let Ok(color) = use_color(
+        color.as_deref(),
+        config.color,
+        |name| env::var_os(name),
+        terminal,
+    ) {
+        colored::control::set_override(color);
+    }
 
-The origin and destination timezones can be either a city,
-such as Europe/London, or a timezone abbreviation, such
-as gmt. A full list of accepted timezones is located at:
+    let (quiet, warnings) = take_quiet_flags(&mut args);
 
-https://github.com/LiquidZulu/timezone/blob/main/src/convert_timezones.rs
+    let settings = Settings {
+        locale,
+        order,
+        config,
+        origin: None,
+        quiet,
+        warnings,
+    };
 
-The day, month, and year are all fairly self-explanatory,
-but you can also specify 'today', 'tomorrow' or 'yesterday'
-for the day.
+    if args.is_empty() || args[0] == "--help" || args[0] == "-h" {
+        return print_help();
+    }
 
-This software is robust, you do not have to fully-specify
-the conversion that you want to perform. At a minimum you
-can specify only the time and the origin, with the rest
-being assumed to be your local timezone, the current day,
-the current month, and the current year.\n"
-        );
-        return;
+    if args[0] == "help" {
+        return match args.get(1) {
+            Some(name) => print_command_help(name),
+            None => print_help(),
+        };
+    }
+
+    if args[1..].iter().any(|arg| arg == "--help" || arg == "-h") {
+        return match find_command(&args[0]) {
+            Some(_) => print_command_help(&args[0]),
+            None => print_help(),
+        };
+    }
+
+    if args[0] == "-i" || args[0] == "--interactive" {
+        return repl::repl(settings);
+    }
+
+    if args[0] == "--batch" {
+        return batch::batch(args[1..].to_vec(), &settings);
+    }
+
+    if args[0] == "--filter" {
+        return filter::filter(args[1..].to_vec(), &settings);
+    }
+
+    run(args, &settings);
+}
+
+// a conversion or subcommand, as given on the command line or
+// entered interactively
+fn run(args: Vec<String>, settings: &Settings) {
+    let args = settings.config.expand_teams(args);
+
+    if args.is_empty() {
+        return println!("\n[{}] there is nothing to convert.\n", "ERROR".red());
+    }
+
+    match args[0].as_str() {
+        "people" => return list_people(&settings.config),
+        "diff" => return diff::diff(args[1..].to_vec(), settings),
+        "offset" => return offset::offset(args[1..].to_vec(), settings),
+        "transitions" => return list_transitions(args[1..].to_vec(), settings),
+        "plan" => return plan::plan(args[1..].to_vec(), settings),
+        "grid" => return grid::grid(args[1..].to_vec(), settings),
+        "logs" => return logs::logs(args[1..].to_vec(), settings),
+        "zones" => return zones::zones(args[1..].to_vec(), settings),
+        "zone" => return zones::zone(args[1..].to_vec(), settings),
+        "tui" => return tui::tui(args[1..].to_vec(), settings),
+        "watch" => return watch::watch(args[1..].to_vec(), settings),
+        "man" => return print!("{}", man_page()),
+        "completions" => return completions::completions(args[1..].to_vec(), settings),
+        _ => {}
     }
 
-    // underspecified
-    if args.len() < 2 {
-        println!(
-            "\n[{}] cannot convert timezones with only {} argument(s) specified.\n",
-            "ERROR".red(),
+    match parse_conversion(args, settings).and_then(|conversion| convert(conversion, settings)) {
+        Ok((given, converted)) => output(given, converted, settings),
+        Err(error) => println!("\n[{}] {error}\n", "ERROR".red()),
+    }
+}
+
+// splits the arguments of a conversion into its parts
+fn parse_conversion(args: Vec<String>, settings: &Settings) -> Result<Conversion, String> {
+    // the time can take up several arguments, eg. 9am to 5pm, in 3 hours
+    let time_end = if args[0] == "in" {
+        1 + (1..args.len().min(4))
+            .rev()
+            .find(|&n| parse_duration(args[1..1 + n].join(" ")).is_some())
+            .unwrap_or_default()
+    } else if args.len() > 2 + settings.origin.is_none() as usize
+        && ["to", "until", "-", "–", "—"].contains(&args[1].as_str())
+        // now - 30m and 9am - 45m are adjustments rather than ranges
+        && !(args[1] == "-"
+            && (args[0] == "now"
+                || (3..=args.len().min(5)).any(|end| parse_duration(args[2..end].join(" ")).is_some())))
+    {
+        3
+    } else {
+        1
+    };
+
+    let time = args[0..time_end].join(" ");
+    let mut rest = args[time_end..].to_vec();
+    let adjustments = take_adjustments(&mut rest);
+
+    // now and in 3 hours are an instant, so they have no origin, and
+    // once set from has been entered interactively it is the origin
+    // of everything after
+    let origin = if parse_relative_time(time.clone()).is_some() {
+        None
+    } else if settings.origin.is_some() {
+        settings.origin.clone()
+    } else if rest.is_empty() {
+        return Err(format!(
+            "cannot convert timezones with only {} argument(s) specified.",
             args.len()
+        ));
+    } else {
+        Some(rest.remove(0))
+    };
+
+    // the first argument after the origin is always a destination,
+    // any that follow it are too until one is not a timezone
+    let destinations_end = match rest.len() {
+        0 | 1 => rest.len(),
+        _ => {
+            1 + rest[1..]
+                .iter()
+                .take_while(|arg| settings.config.lookup_timezone(arg).is_some())
+                .count()
+        }
+    };
+
+    Ok(Conversion {
+        time,
+        origin,
+        destinations: rest[..destinations_end].to_vec(),
+        adjustments,
+        date: if rest.len() > destinations_end {
+            Some(rest[destinations_end..].join(" "))
+        } else {
+            None
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::settings;
+
+    fn converted(args: &str) -> Vec<String> {
+        let settings = settings();
+        let args = args.split_whitespace().map(String::from).collect();
+        let (_, converted) =
+            convert(parse_conversion(args, &settings).unwrap(), &settings).unwrap();
+
+        converted
+            .into_iter()
+            .map(|converted| converted.time)
+            .collect()
+    }
+
+    fn color(
+        flag: Option<&str>,
+        config: Option<bool>,
+        vars: &[(&str, &str)],
+        terminal: bool,
+    ) -> Result<bool, String> {
+        let var = |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| OsString::from(value))
+        };
+
+        use_color(flag, config, var, terminal)
+    }
+
+    #[test]
+    fn color_follows_the_flag_then_the_config_then_the_environment() {
+        let force = [("CLICOLOR_FORCE", "1")];
+
+        assert_eq!(color(Some("never"), Some(true), &force, true), Ok(false));
+        assert_eq!(color(Some("always"), Some(false), &[], false), Ok(true));
+        assert_eq!(color(Some("auto"), Some(false), &[], true), Ok(true));
+        assert!(color(Some("sometimes"), None, &[], true).is_err());
+
+        assert_eq!(color(None, Some(false), &force, true), Ok(false));
+        assert_eq!(
+            color(None, Some(true), &[("NO_COLOR", "1")], false),
+            Ok(true)
+        );
+
+        assert_eq!(
+            color(None, None, &[("NO_COLOR", "1"), force[0]], true),
+            Ok(false)
+        );
+        assert_eq!(color(None, None, &[("NO_COLOR", "")], true), Ok(true));
+        assert_eq!(color(None, None, &force, false), Ok(true));
+        assert_eq!(
+            color(None, None, &[("CLICOLOR_FORCE", "0")], false),
+            Ok(false)
         );
-        return;
+        assert_eq!(color(None, None, &[("CLICOLOR", "0")], true), Ok(false));
+        assert_eq!(color(None, None, &[], true), Ok(true));
+        assert_eq!(color(None, None, &[], false), Ok(false));
     }
 
-    // overspecified
-    if args.len() > 6 {
-        println!(
-            "\n[{}] you have overspecified the conversion.\n\n\tExpected: time origin_timezone destination_timezone day month year\n\tGot:      {} {}\n",
-            "WARNING".yellow(),
-            args[0..5].join(" "),
-            args[6..].join(" ").red()
+    #[test]
+    fn quiet_flags_are_taken_from_before_the_command() {
+        let mut args: Vec<String> = ["-q", "--no-warnings", "--batch", "-q"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(take_quiet_flags(&mut args), (true, false));
+        assert_eq!(args, ["--batch", "-q"]);
+
+        let mut args: Vec<String> = ["--no-warnings", "9am", "et"].map(String::from).to_vec();
+        assert_eq!(take_quiet_flags(&mut args), (false, false));
+        assert_eq!(args, ["9am", "et"]);
+
+        let mut args: Vec<String> = ["logs", "--quiet"].map(String::from).to_vec();
+        assert_eq!(take_quiet_flags(&mut args), (false, true));
+        assert_eq!(args, ["logs", "--quiet"]);
+    }
+
+    #[test]
+    fn quiet_output_is_only_the_times() {
+        let mut settings = settings();
+        settings.quiet = true;
+        let args = "9am utc Europe/London Asia/Tokyo 1 jun 2025"
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        let (given, converted) =
+            convert(parse_conversion(args, &settings).unwrap(), &settings).unwrap();
+
+        assert_eq!(
+            output_text(&given, &converted, &settings),
+            "10:00am\n6:00pm\n"
         );
+
+        settings.quiet = false;
+        assert!(output_text(&given, &converted, &settings).contains("is 10:00am Europe/London"));
     }
 
-    match args.len() {
-        2 => convert(
-            args[0].clone(),
-            args[1].clone(),
-            None,
-            None,
-            None,
-            None,
-        ),
-        3 => convert(
-            args[0].clone(),
-            args[1].clone(),
-            Some(args[2].clone()),
-            None,
-            None,
-            None,
-        ),
-        4 => convert(
-            args[0].clone(),
-            args[1].clone(),
-            Some(args[2].clone()),
-            Some(args[3].clone()),
-            None,
-            None,
-        ),
-        5 => convert(
-            args[0].clone(),
-            args[1].clone(),
-            Some(args[2].clone()),
-            Some(args[3].clone()),
-            Some(args[4].clone()),
-            None,
-        ),
-        6.. => convert(
-            args[0].clone(),
-            args[1].clone(),
-            Some(args[2].clone()),
-            Some(args[3].clone()),
-            Some(args[4].clone()),
-            Some(args[5].clone()),
-        ),
-        _ => unreachable!("[{}] args.len() not covered by match statement. This should never happen, if you see this please report this error at https://github.com/LiquidZulu/timezone/issues", "ERROR".red()),
-    };
+    #[test]
+    fn ranges_past_midnight_end_on_the_next_day_across_daylight_saving() {
+        // the clocks go forward in London, and back in New York, overnight
+        assert_eq!(
+            converted("10pm-2am Europe/London utc 29 mar 2025"),
+            ["10:00pm-1:00am (+1)"]
+        );
+        assert_eq!(
+            converted("10pm-4am America/New_York utc 1 nov 2025"),
+            ["2:00am-9:00am"]
+        );
+        assert_eq!(
+            converted("10pm-2am utc Europe/London 1 jan 2025"),
+            ["10:00pm-2:00am (+1)"]
+        );
+    }
 }
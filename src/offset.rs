@@ -0,0 +1,169 @@
+use crate::{format_duration, transitions, utc_offset, Settings};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use colored::*;
+
+// how far ahead of `from` the clocks in `to` are at `instant`
+pub fn offset_difference(from: Tz, to: Tz, instant: DateTime<Utc>) -> Duration {
+    Duration::seconds(
+        (utc_offset(to, instant).local_minus_utc() - utc_offset(from, instant).local_minus_utc())
+            .into(),
+    )
+}
+
+// the instants between `start` and `end` at which the difference
+// between `from` and `to` changes, with the new difference
+pub fn offset_difference_changes(
+    from: Tz,
+    to: Tz,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Vec<(DateTime<Utc>, Duration)> {
+    let mut instants: Vec<DateTime<Utc>> =
+        [transitions(from, start, end), transitions(to, start, end)]
+            .concat()
+            .iter()
+            .map(|transition| transition.instant)
+            .collect();
+    instants.sort();
+
+    let mut changes: Vec<(DateTime<Utc>, Duration)> = vec![];
+    let mut current = offset_difference(from, to, start);
+
+    for instant in instants {
+        let difference = offset_difference(from, to, instant);
+
+        // both zones changing at once can leave the difference as it was
+        if difference != current {
+            changes.push((instant, difference));
+            current = difference;
+        }
+    }
+
+    changes
+}
+
+// 12h30m ahead, 5h behind
+fn describe(difference: Duration) -> String {
+    if difference > Duration::zero() {
+        format!("{} ahead", format_duration(difference))
+    } else if difference < Duration::zero() {
+        format!("{} behind", format_duration(-difference))
+    } else {
+        "the same".to_string()
+    }
+}
+
+// abbreviations are shouted, IANA names and people are left as
+// they are
+pub fn display_name(name: &str) -> String {
+    if name.contains('/') || name.starts_with('@') {
+        name.to_string()
+    } else {
+        name.to_uppercase()
+    }
+}
+
+/*
+ * pt ist - how far ahead of PT the clocks in IST are, now and
+ *          over the coming year
+ */
+pub fn offset(args: Vec<String>, settings: &Settings) {
+    if args.len() != 2 {
+        println!(
+            "\n[{}] expected two timezones to compare.\n\n\tUsage: tz offset timezone timezone\n\tExample: tz offset pt ist\n",
+            "ERROR".red()
+        );
+        return;
+    }
+
+    let (from_name, to_name) = (args[0].clone(), args[1].clone());
+
+    let (from, to) = match (
+        settings.config.lookup_timezone(&from_name),
+        settings.config.lookup_timezone(&to_name),
+    ) {
+        (Some(from), Some(to)) => (from, to),
+        (None, _) => {
+            println!("\n[{}] cannot parse timezone {from_name}\n", "ERROR".red());
+            return;
+        }
+        (_, None) => {
+            println!("\n[{}] cannot parse timezone {to_name}\n", "ERROR".red());
+            return;
+        }
+    };
+
+    let now = Utc::now();
+    let changes = offset_difference_changes(from, to, now, now + Duration::days(365));
+
+    println!("\n");
+    let difference = offset_difference(from, to, now);
+
+    println!(
+        "{} is {} {} {} right now",
+        display_name(&to_name),
+        describe(difference).cyan(),
+        if difference == Duration::zero() {
+            "as"
+        } else {
+            "of"
+        },
+        display_name(&from_name)
+    );
+
+    if changes.is_empty() {
+        println!("and this does not change over the coming year");
+    } else {
+        println!();
+    }
+
+    for (i, (instant, difference)) in changes.iter().enumerate() {
+        let starting = instant.with_timezone(&from).format("%-d %b %Y");
+
+        match changes.get(i + 1) {
+            Some((until, _)) => println!(
+                "\t{} from {starting} to {}",
+                describe(*difference),
+                until.with_timezone(&from).format("%-d %b %Y")
+            ),
+            None => println!("\t{} from {starting}", describe(*difference)),
+        }
+    }
+    println!("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{utc, zone};
+
+    #[test]
+    fn pt_and_bst_change_on_different_days() {
+        let pt = zone("America/Los_Angeles");
+        let bst = zone("Europe/London");
+        let (start, end) = (utc(2025, 1, 1, 0), utc(2026, 1, 1, 0));
+
+        assert_eq!(offset_difference(pt, bst, start), Duration::hours(8));
+        assert_eq!(
+            offset_difference_changes(pt, bst, start, end),
+            vec![
+                (utc(2025, 3, 9, 10), Duration::hours(7)),
+                (utc(2025, 3, 30, 1), Duration::hours(8)),
+                (utc(2025, 10, 26, 1), Duration::hours(7)),
+                (utc(2025, 11, 2, 9), Duration::hours(8)),
+            ]
+        );
+    }
+
+    #[test]
+    fn zones_changing_together_keep_their_difference() {
+        let london = zone("Europe/London");
+        let paris = zone("Europe/Paris");
+
+        assert_eq!(
+            offset_difference_changes(london, paris, utc(2025, 1, 1, 0), utc(2026, 1, 1, 0)),
+            vec![]
+        );
+    }
+}
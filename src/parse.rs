@@ -1,8 +1,11 @@
+use crate::DateOrder;
+use crate::Locale;
+use crate::Settings;
 use crate::Time;
 use crate::TimeFormat;
 use crate::TimeFormat::*;
 use crate::TZ_MAP;
-use chrono::{Datelike, Duration, NaiveDate, Offset, TimeZone};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Offset, TimeZone, Weekday};
 use chrono_tz::Tz;
 use colored::*;
 use regex::Regex;
@@ -58,10 +61,12 @@ pub fn parse_time(time: String) -> Option<(u32, u32)> {
                 };
 
                 match hours_string.parse::<u32>() {
-                    Ok(hours_int) => match Time::hours(hours_int) {
-                        Some(Time::Hours(h)) => Some((h + offset, 0)),
-                        _ => None,
-                    },
+                    Ok(hours_int) if hours_int <= 12 => {
+                        match Time::hours(hours_int % 12 + offset) {
+                            Some(Time::Hours(h)) => Some((h, 0)),
+                            _ => None,
+                        }
+                    }
                     _ => None,
                 }
             }
@@ -85,10 +90,14 @@ pub fn parse_time(time: String) -> Option<(u32, u32)> {
                         let maybe_minutes = &hm[1];
 
                         match (maybe_hours, maybe_minutes) {
-                            (Ok(h), Ok(m)) => match (Time::hours(h + offset), Time::minutes(*m)) {
-                                (Some(Time::Hours(hr)), Some(Time::Minutes(mn))) => Some((hr, mn)),
-                                _ => None,
-                            },
+                            (Ok(h), Ok(m)) if *h <= 12 => {
+                                match (Time::hours(h % 12 + offset), Time::minutes(*m)) {
+                                    (Some(Time::Hours(hr)), Some(Time::Minutes(mn))) => {
+                                        Some((hr, mn))
+                                    }
+                                    _ => None,
+                                }
+                            }
                             _ => None,
                         }
                     }
@@ -128,133 +137,636 @@ pub fn parse_time(time: String) -> Option<(u32, u32)> {
     }
 }
 
-pub fn parse_timezone(origin: String, destination: Option<String>) -> (Option<Tz>, Option<Tz>) {
-    (
-        TZ_MAP.get(&origin).copied(),
-        match destination {
-            Some(ref d) => TZ_MAP.get(&d).copied(),
+fn duration_unit(unit: &str) -> Option<Duration> {
+    match unit {
+        "w" | "wk" | "wks" | "week" | "weeks" => Some(Duration::weeks(1)),
+        "d" | "day" | "days" => Some(Duration::days(1)),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(Duration::hours(1)),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(Duration::minutes(1)),
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(Duration::seconds(1)),
+        _ => None,
+    }
+}
+
+/*
+ * 90m, 1h30m, 2d, 1w           - compact
+ * 3 hours, 1 hour 30 minutes   - spelled out
+ * PT1H30M, P1DT12H             - ISO 8601
+ */
+pub fn parse_duration(duration: String) -> Option<Duration> {
+    let duration = duration.to_lowercase();
+
+    let iso_regex: Regex =
+        Regex::new(r"^p(?:(\d+)w)?(?:(\d+)d)?(?:t(?:(\d+)h)?(?:(\d+)m)?(?:(\d+)s)?)?$").unwrap();
+    let spelled_regex: Regex = Regex::new(r"^(\s*\d+\s*[a-z]+)+\s*$").unwrap();
+    let part_regex: Regex = Regex::new(r"(\d+)\s*([a-z]+)").unwrap();
+
+    if let Some(captures) = iso_regex.captures(&duration) {
+        let units = ["w", "d", "h", "m", "s"];
+
+        // P and PT on their own are not durations
+        if captures.iter().skip(1).all(|capture| capture.is_none()) {
+            return None;
+        }
+
+        return units
+            .iter()
+            .enumerate()
+            .filter_map(|(i, unit)| Some((captures.get(i + 1)?, unit)))
+            .map(|(n, unit)| duration_unit(unit)?.checked_mul(n.as_str().parse().ok()?))
+            .try_fold(Duration::zero(), |total, part| total.checked_add(&part?));
+    }
+
+    if !spelled_regex.is_match(&duration) {
+        return None;
+    }
+
+    let total = part_regex
+        .captures_iter(&duration)
+        .map(|captures| duration_unit(&captures[2])?.checked_mul(captures[1].parse().ok()?))
+        .try_fold(Duration::zero(), |total, part| total.checked_add(&part?));
+    total
+}
+
+// + 45m, -1h30m
+pub fn parse_adjustment(adjustment: String) -> Option<Duration> {
+    let adjustment = adjustment.trim();
+
+    match adjustment.chars().next() {
+        Some('+') => parse_duration(adjustment[1..].to_string()),
+        Some('-') => parse_duration(adjustment[1..].to_string()).map(|d| -d),
+        _ => None,
+    }
+}
+
+// now, in 3 hours - how far from the current instant the time is
+pub fn parse_relative_time(time: String) -> Option<Duration> {
+    let time = time.to_lowercase();
+
+    if time == "now" {
+        return Some(Duration::zero());
+    }
+
+    parse_duration(time.strip_prefix("in ")?.to_string())
+}
+
+// separators between the start and end of a time range, in the
+// order that they are tried
+static RANGE_SEPARATORS: [&str; 5] = [" to ", " until ", "-", "–", "—"];
+
+/*
+ * 9am-5pm, 13:00–14:30, 9am to 5pm - a start and an end time
+ * 9-5pm, 11-1pm                    - the start takes the am/pm of the end
+ *                                    unless that would put it after the end
+ */
+pub fn parse_time_range(range: String) -> Option<((u32, u32), (u32, u32))> {
+    let (start, end) = RANGE_SEPARATORS
+        .iter()
+        .find_map(|separator| range.split_once(separator))?;
+
+    let (start, end) = (start.trim(), end.trim());
+    let end_time = parse_time(end.to_string())?;
+
+    if let Some(start_time) = parse_time(start.to_string()) {
+        return Some((start_time, end_time));
+    }
+
+    let (same, other) = match &end[end.len().saturating_sub(2)..] {
+        "am" => ("am", "pm"),
+        "pm" => ("pm", "am"),
+        _ => return None,
+    };
+
+    let start_time = match (
+        parse_time(format!("{start}{same}")),
+        parse_time(format!("{start}{other}")),
+    ) {
+        (Some(start_time), _) if start_time <= end_time => start_time,
+        (_, Some(start_time)) => start_time,
+        (start_time, None) => start_time?,
+    };
+
+    Some((start_time, end_time))
+}
+
+// 9-17, 9am-5pm, 09:30-17:30 - the hours of a working day
+pub fn parse_hours(hours: String) -> Option<(NaiveTime, NaiveTime)> {
+    let ((start_hours, start_minutes), (end_hours, end_minutes)) =
+        match parse_time_range(hours.to_lowercase()) {
+            Some(range) => range,
             None => {
-                let offset = chrono::Local
-                    .timestamp_opt(0, 0)
-                    .unwrap()
-                    .offset()
-                    .fix()
-                    .local_minus_utc()
-                    / 60
-                    / 60;
-                let local = if offset < 0 {
-                    format!("utc-{offset}")
-                } else {
-                    format!("utc+{offset}")
-                };
-                println!(
-                    "\n[{}] cannot parse destination {:?}. Using local timezone = {local}.",
-                    "WARNING".yellow(),
-                    destination
-                );
-                TZ_MAP.get(&local).copied()
+                let (start, end) = hours.split_once('-')?;
+                (
+                    (start.parse::<u32>().ok()?, 0),
+                    (end.parse::<u32>().ok()?, 0),
+                )
             }
+        };
+
+    let start = NaiveTime::from_hms_opt(start_hours, start_minutes, 0)?;
+    let end = NaiveTime::from_hms_opt(end_hours, end_minutes, 0)?;
+
+    if start < end {
+        Some((start, end))
+    } else {
+        None
+    }
+}
+
+// IANA names are case sensitive, abbreviations are not
+pub fn lookup_timezone(name: &str) -> Option<Tz> {
+    TZ_MAP
+        .get(name)
+        .or_else(|| TZ_MAP.get(name.to_lowercase().as_str()))
+        .copied()
+}
+
+// people such as @alice are looked up in `config` first
+pub fn parse_timezone(
+    origin: String,
+    destination: Option<String>,
+    settings: &Settings,
+) -> (Option<Tz>, Option<Tz>) {
+    let config = &settings.config;
+
+    (
+        config.lookup_timezone(&origin),
+        match destination {
+            Some(ref d) => config.lookup_timezone(d),
+            None => match &config.local {
+                Some(local) => config.lookup_timezone(local),
+                None => {
+                    let local = local_timezone();
+                    if settings.warnings {
+                        println!(
+                            "\n[{}] cannot parse destination {:?}. Using local timezone = {local}.",
+                            "WARNING".yellow(),
+                            destination
+                        );
+                    }
+                    lookup_timezone(&local)
+                }
+            },
         },
     )
 }
 
-pub fn parse_day(maybe_day: Option<String>) -> Option<u32> {
-    if maybe_day == None {
-        return parse_day(Some("today".to_string()));
-    }
+// the name of the local timezone, from $TZ or /etc/localtime, or
+// else the current UTC offset, eg. utc+1
+pub fn local_timezone() -> String {
+    let from_env = std::env::var("TZ")
+        .ok()
+        .map(|tz| tz.trim_start_matches(':').to_string());
+    let from_localtime = std::fs::read_link("/etc/localtime").ok().and_then(|path| {
+        let path = path.to_string_lossy().to_string();
+        Some(path.split_once("zoneinfo/")?.1.to_string())
+    });
 
-    let day = maybe_day.unwrap().to_lowercase();
-    let today = chrono::Utc::now();
+    if let Some(name) = [from_env, from_localtime]
+        .into_iter()
+        .flatten()
+        .find(|name| lookup_timezone(name).is_some())
+    {
+        return name;
+    }
 
-    if day == "today" {
-        return Some(today.day());
+    match chrono::Local::now().offset().fix().local_minus_utc() / 60 / 60 {
+        0 => "utc".to_string(),
+        hours => format!("utc{hours:+}"),
     }
+}
+
+// the first `weekday` on or after `from`
+fn weekday_on_or_after(from: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days_ahead =
+        (7 + weekday.num_days_from_monday() - from.weekday().num_days_from_monday()) % 7;
+    from + Duration::days(days_ahead.into())
+}
 
-    if day == "yesterday" {
-        return Some((today - Duration::days(1)).day());
+// the first `weekday` on or before `from`
+fn weekday_on_or_before(from: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days_behind =
+        (7 + from.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    from - Duration::days(days_behind.into())
+}
+
+fn parse_days_offset(offset: &str) -> Option<i64> {
+    match offset.chars().next() {
+        Some('+') => offset[1..].parse::<i64>().ok(),
+        Some('-') => offset[1..].parse::<i64>().ok().map(|n| -n),
+        _ => None,
     }
+}
+
+/*
+ * today, yesterday, tomorrow       - relative to the current day, see Locale
+ * day after tomorrow               - also: day before yesterday, demain, übermorgen
+ * monday, next friday, last tue    - the coming, following, or previous weekday
+ * next week, last week             - the same weekday a week away
+ * in 3 days, in 2 weeks, +2, -1    - an offset in days or weeks
+ * 2024-03-20                       - an ISO 8601 date
+ * 20                               - the nth day of the current month
+ */
+pub fn parse_day(
+    maybe_day: Option<String>,
+    locale: &Locale,
+    today: NaiveDate,
+) -> Option<NaiveDate> {
+    let day = match maybe_day {
+        Some(day) => day.to_lowercase(),
+        None => return Some(today),
+    };
 
-    if day == "tomorrow" {
-        return Some((today + Duration::days(1)).day());
+    let words: Vec<&str> = day.split_whitespace().collect();
+
+    if let Some(offset) = locale.day_offset(&words.join(" ")) {
+        return today.checked_add_signed(Duration::try_days(offset)?);
     }
 
-    match day.parse::<u32>() {
-        Ok(n) => {
-            match NaiveDate::from_ymd_opt(today.year(), today.month(), n.try_into().unwrap()) {
-                Some(date) => Some(date.day().try_into().unwrap()),
-                None => None,
+    match words[..] {
+        ["next", "week"] => today.checked_add_signed(Duration::weeks(1)),
+        ["last", "week"] => today.checked_sub_signed(Duration::weeks(1)),
+        ["in", n, "day" | "days"] => today.checked_add_signed(Duration::try_days(n.parse().ok()?)?),
+        ["in", n, "week" | "weeks"] => {
+            today.checked_add_signed(Duration::try_weeks(n.parse().ok()?)?)
+        }
+        ["next", weekday] => Some(weekday_on_or_after(
+            today.succ_opt()?,
+            locale.weekday(weekday)?,
+        )),
+        ["last", weekday] => Some(weekday_on_or_before(
+            today.pred_opt()?,
+            locale.weekday(weekday)?,
+        )),
+        [word] => {
+            if let Some(weekday) = locale.weekday(word) {
+                return Some(weekday_on_or_after(today, weekday));
+            }
+
+            if let Some(offset) = parse_days_offset(word) {
+                return today.checked_add_signed(Duration::try_days(offset)?);
+            }
+
+            if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+                return Some(date);
+            }
+
+            match word.parse::<u32>() {
+                Ok(n) => NaiveDate::from_ymd_opt(today.year(), today.month(), n),
+                Err(_) => None,
             }
         }
-        Err(_) => None,
+        _ => None,
     }
 }
 
-static MONTH_MAP: phf::Map<&'static str, u32> = phf::phf_map! {
-    "jan" => 1,
-    "january" => 1,
-    "feb" => 2,
-    "february" => 2,
-    "mar" => 3,
-    "march" => 3,
-    "apr" => 4,
-    "april" => 4,
-    "may" => 5,
-    "jun" => 6,
-    "june" => 6,
-    "jul" => 7,
-    "july" => 7,
-    "aug" => 8,
-    "august" => 8,
-    "sep" => 9,
-    "sept" => 9,
-    "september" => 9,
-    "oct" => 10,
-    "october" => 10,
-    "nov" => 11,
-    "november" => 11,
-    "dec" => 12,
-    "december" => 12,
-    "1" => 1,
-    "01" => 1,
-    "2" => 2,
-    "02" => 2,
-    "3" => 3,
-    "03" => 3,
-    "4" => 4,
-    "04" => 4,
-    "5" => 5,
-    "05" => 5,
-    "6" => 6,
-    "06" => 6,
-    "7" => 7,
-    "07" => 7,
-    "8" => 8,
-    "08" => 8,
-    "9" => 9,
-    "09" => 9,
-    "10" => 10,
-    "11" => 11,
-    "12" => 12,
-};
-
-pub fn parse_month(maybe_month: Option<String>) -> Option<u32> {
-    if maybe_month == None {
-        return parse_month(Some(chrono::Utc::now().month().to_string()));
-    }
-
-    return MONTH_MAP.get(&maybe_month.unwrap()).copied();
+pub fn parse_month(maybe_month: Option<String>, locale: &Locale) -> Option<u32> {
+    match maybe_month {
+        Some(month) => locale.month(&month.to_lowercase()),
+        None => Some(chrono::Utc::now().month()),
+    }
 }
 
-pub fn parse_year(maybe_year: Option<String>) -> Option<i32> {
-    if maybe_year == None {
-        return parse_year(Some(chrono::Utc::now().year().to_string()));
+pub fn today() -> NaiveDate {
+    chrono::Utc::now().date_naive()
+}
+
+// 2 digit years are taken to be in the 2000s
+fn parse_short_year(year: &str) -> Option<i32> {
+    match year.len() {
+        2 => year.parse::<i32>().ok().map(|n| 2000 + n),
+        _ => parse_year(Some(year.to_string())),
+    }
+}
+
+// a numeric day and month whose order is only known by preference,
+// falling back on the other order when the preferred one is invalid
+fn ambiguous_date(first: u32, second: u32, year: i32, order: DateOrder) -> Option<NaiveDate> {
+    let (day_first, month_first) = (
+        NaiveDate::from_ymd_opt(year, second, first),
+        NaiveDate::from_ymd_opt(year, first, second),
+    );
+
+    match order {
+        DateOrder::DayFirst => day_first.or(month_first),
+        DateOrder::MonthFirst => month_first.or(day_first),
+    }
+}
+
+/*
+ * 20               - the nth day of the current month
+ * 20/02, 2/20      - day and month in either order, see DateOrder
+ * 20.02.2024       - also: 20/02/24, 20-02-2024, 02/20/2024
+ * 2024/02/20       - a leading 4 digit year is always year-month-day
+ */
+fn parse_numeric_date(date: &str, order: DateOrder, today: NaiveDate) -> Option<NaiveDate> {
+    let parts: Vec<&str> = date.split(['/', '.', '-']).collect();
+
+    let numbers: Vec<u32> = parts
+        .iter()
+        .map(|part| part.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+
+    match (&parts[..], &numbers[..]) {
+        (_, [day]) => NaiveDate::from_ymd_opt(today.year(), today.month(), *day),
+        (_, [first, second]) => ambiguous_date(*first, *second, today.year(), order),
+        ([year, _, _], [_, month, day]) if year.len() == 4 => {
+            NaiveDate::from_ymd_opt(parse_short_year(year)?, *month, *day)
+        }
+        ([_, _, year], [first, second, _]) => {
+            ambiguous_date(*first, *second, parse_short_year(year)?, order)
+        }
+        _ => None,
     }
+}
+
+/*
+ * 20 feb, feb 20               - assumes the year of `today`
+ * 20th feb 2020, feb 20th 2020 - ordinals and either order
+ * the 20th of february, 2020   - with filler words and commas
+ * 5 de mayo, 3. März, 1er mai  - in the selected locale
+ * 20 02 2020                   - numbers only, read as a numeric date
+ */
+fn parse_worded_date(
+    tokens: &[&str],
+    locale: &Locale,
+    order: DateOrder,
+    today: NaiveDate,
+) -> Option<NaiveDate> {
+    let maybe_month_index = tokens
+        .iter()
+        .position(|token| token.chars().all(char::is_alphabetic) && locale.month(token).is_some());
+
+    let month_index = match maybe_month_index {
+        Some(i) => i,
+        None => return parse_numeric_date(&tokens.join("/"), order, today),
+    };
+
+    let month_n = parse_month(Some(tokens[month_index].to_string()), locale)?;
+
+    let numbers: Vec<&str> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != month_index)
+        .map(|(_, token)| *token)
+        .collect();
+
+    let (day, year) = match numbers[..] {
+        [day] => (day, None),
+        [year, day] if year.len() == 4 => (day, Some(year)),
+        [day, year] => (day, Some(year)),
+        _ => return None,
+    };
+
+    let year_n = match year {
+        Some(year) => parse_short_year(year)?,
+        None => today.year(),
+    };
+
+    NaiveDate::from_ymd_opt(year_n, month_n, day.parse::<u32>().ok()?)
+}
+
+/*
+ * tomorrow           - see parse_day for the relative expressions
+ * 20th feb           - see parse_worded_date
+ * 20/02/2024         - see parse_numeric_date
+ *
+ * anything not given is assumed from `today`
+ */
+pub fn parse_date(
+    maybe_date: Option<String>,
+    locale: &Locale,
+    order: DateOrder,
+    today: NaiveDate,
+) -> Option<NaiveDate> {
+    let date = match maybe_date {
+        Some(date) => date.to_lowercase(),
+        None => return Some(today),
+    };
+
+    if let Some(relative) = parse_day(Some(date.clone()), locale, today) {
+        return Some(relative);
+    }
+
+    let tokens: Vec<&str> = date
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty() && !locale.is_filler(token))
+        .map(|token| locale.strip_ordinal(token))
+        .collect();
+
+    match tokens[..] {
+        [] => None,
+        [token] => parse_numeric_date(token, order, today),
+        _ => parse_worded_date(&tokens, locale, order, today),
+    }
+}
+
+// 9am pt friday, now pt - a time in a timezone on a date, as
+// written for a conversion, with the date relative to `today`
+pub fn parse_zoned_time(
+    time: String,
+    timezone: Tz,
+    date: Option<String>,
+    locale: &Locale,
+    order: DateOrder,
+    today: NaiveDate,
+) -> Option<DateTime<Tz>> {
+    if let Some(from_now) = parse_relative_time(time.clone()) {
+        return match date {
+            Some(_) => None,
+            None => Some(
+                chrono::Utc::now()
+                    .checked_add_signed(from_now)?
+                    .with_timezone(&timezone),
+            ),
+        };
+    }
+
+    let (hours, minutes) = parse_time(time.to_lowercase())?;
+    let naive_date = parse_date(date, locale, order, today)?;
+
+    timezone
+        .from_local_datetime(&naive_date.and_hms_opt(hours, minutes, 0)?)
+        .earliest()
+}
 
-    match maybe_year.unwrap().parse::<i32>() {
-        Ok(n) => match NaiveDate::from_ymd_opt(n, 1, 1) {
-            Some(date) => Some(date.year().try_into().unwrap()),
-            None => None,
+pub fn parse_year(maybe_year: Option<String>) -> Option<i32> {
+    match maybe_year {
+        Some(year) => match year.parse::<i32>() {
+            Ok(n) => NaiveDate::from_ymd_opt(n, 1, 1).map(|date| date.year()),
+            Err(_) => None,
         },
-        Err(_) => None,
+        None => Some(chrono::Utc::now().year()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::*;
+    use crate::testing::date;
+
+    fn day(expression: &str, today: NaiveDate) -> Option<NaiveDate> {
+        parse_date(
+            Some(expression.to_string()),
+            &ENGLISH,
+            DateOrder::DayFirst,
+            today,
+        )
+    }
+
+    #[test]
+    fn relative_days_roll_over_month_end() {
+        assert_eq!(day("tomorrow", date(2024, 1, 31)), Some(date(2024, 2, 1)));
+        assert_eq!(day("yesterday", date(2024, 3, 1)), Some(date(2024, 2, 29)));
+        assert_eq!(day("yesterday", date(2023, 3, 1)), Some(date(2023, 2, 28)));
+        assert_eq!(
+            day("day after tomorrow", date(2024, 4, 29)),
+            Some(date(2024, 5, 1))
+        );
+        assert_eq!(
+            day("next friday", date(2024, 1, 29)),
+            Some(date(2024, 2, 2))
+        );
+    }
+
+    #[test]
+    fn relative_days_roll_over_year_end() {
+        assert_eq!(day("tomorrow", date(2023, 12, 31)), Some(date(2024, 1, 1)));
+        assert_eq!(day("yesterday", date(2024, 1, 1)), Some(date(2023, 12, 31)));
+        assert_eq!(day("next week", date(2024, 12, 28)), Some(date(2025, 1, 4)));
+        assert_eq!(day("in 3 days", date(2024, 12, 30)), Some(date(2025, 1, 2)));
+        assert_eq!(day("last tue", date(2025, 1, 2)), Some(date(2024, 12, 31)));
+    }
+
+    #[test]
+    fn huge_day_offsets_do_not_parse() {
+        let today = date(2024, 1, 31);
+
+        assert_eq!(day("+99999999999999", today), None);
+        assert_eq!(day("in 99999999999999 days", today), None);
+        assert_eq!(day("in 99999999999999 weeks", today), None);
+        assert_eq!(day("+2", today), Some(date(2024, 2, 2)));
+    }
+
+    #[test]
+    fn explicit_dates() {
+        let today = date(2024, 1, 31);
+
+        assert_eq!(day("20 feb", today), Some(date(2024, 2, 20)));
+        assert_eq!(day("20 feb 2020", today), Some(date(2020, 2, 20)));
+        assert_eq!(day("30 feb", today), None);
+        assert_eq!(day("tomorrow feb", today), None);
+    }
+
+    #[test]
+    fn flexible_dates() {
+        let today = date(2024, 1, 31);
+
+        assert_eq!(day("20th feb", today), Some(date(2024, 2, 20)));
+        assert_eq!(day("feb 20", today), Some(date(2024, 2, 20)));
+        assert_eq!(day("february 1st, 2025", today), Some(date(2025, 2, 1)));
+        assert_eq!(day("the 3rd of march 2020", today), Some(date(2020, 3, 3)));
+        assert_eq!(day("20/02/2024", today), Some(date(2024, 2, 20)));
+        assert_eq!(day("20.02.2024", today), Some(date(2024, 2, 20)));
+        assert_eq!(day("2024/02/20", today), Some(date(2024, 2, 20)));
+        assert_eq!(day("20 2 24", today), Some(date(2024, 2, 20)));
+        assert_eq!(day("2/20", today), Some(date(2024, 2, 20)));
+        assert_eq!(day("20th", today), Some(date(2024, 1, 20)));
+    }
+
+    #[test]
+    fn ambiguous_dates_follow_preference() {
+        let today = date(2024, 1, 31);
+        let month_first =
+            |d: &str| parse_date(Some(d.to_string()), &ENGLISH, DateOrder::MonthFirst, today);
+
+        assert_eq!(day("02/03", today), Some(date(2024, 3, 2)));
+        assert_eq!(month_first("02/03"), Some(date(2024, 2, 3)));
+        assert_eq!(month_first("02/03/2020"), Some(date(2020, 2, 3)));
+        assert_eq!(month_first("20/02/2020"), Some(date(2020, 2, 20)));
+    }
+
+    #[test]
+    fn localised_dates() {
+        let today = date(2024, 1, 31);
+        let localised = |d: &str, code: &str| {
+            parse_date(
+                Some(d.to_string()),
+                Locale::from_code(code).unwrap(),
+                DateOrder::DayFirst,
+                today,
+            )
+        };
+
+        assert_eq!(
+            localised("20 février", "fr_FR.UTF-8"),
+            Some(date(2024, 2, 20))
+        );
+        assert_eq!(localised("1er mai 2025", "fr"), Some(date(2025, 5, 1)));
+        assert_eq!(localised("3. März", "de"), Some(date(2024, 3, 3)));
+        assert_eq!(localised("5 de mayo", "es"), Some(date(2024, 5, 5)));
+        assert_eq!(localised("5 de maio", "pt"), Some(date(2024, 5, 5)));
+        assert_eq!(localised("2 agosto", "it"), Some(date(2024, 8, 2)));
+        assert_eq!(localised("3 maart", "nl"), Some(date(2024, 3, 3)));
+        assert_eq!(localised("demain", "fr"), Some(date(2024, 2, 1)));
+        assert_eq!(localised("pasado mañana", "es"), Some(date(2024, 2, 2)));
+        assert_eq!(localised("freitag", "de"), Some(date(2024, 2, 2)));
+        assert_eq!(localised("tomorrow", "nl"), Some(date(2024, 2, 1)));
+    }
+
+    #[test]
+    fn twelve_am_is_midnight_and_twelve_pm_is_noon() {
+        let time = |t: &str| parse_time(t.to_string());
+
+        assert_eq!(time("12am"), Some((0, 0)));
+        assert_eq!(time("12pm"), Some((12, 0)));
+        assert_eq!(time("12:30am"), Some((0, 30)));
+        assert_eq!(time("12:30pm"), Some((12, 30)));
+        assert_eq!(time("1am"), Some((1, 0)));
+        assert_eq!(time("11pm"), Some((23, 0)));
+        assert_eq!(time("13pm"), None);
+    }
+
+    #[test]
+    fn time_ranges() {
+        let range = |r: &str| parse_time_range(r.to_string());
+
+        assert_eq!(range("9am-5pm"), Some(((9, 0), (17, 0))));
+        assert_eq!(range("9-5pm"), Some(((9, 0), (17, 0))));
+        assert_eq!(range("11-1pm"), Some(((11, 0), (13, 0))));
+        assert_eq!(range("7-9pm"), Some(((19, 0), (21, 0))));
+        assert_eq!(range("13:00–14:30"), Some(((13, 0), (14, 30))));
+        assert_eq!(range("9am to 12pm"), Some(((9, 0), (12, 0))));
+        assert_eq!(range("10pm-2am"), Some(((22, 0), (2, 0))));
+        assert_eq!(range("9am"), None);
+        assert_eq!(range("9-17"), None);
+    }
+
+    #[test]
+    fn durations() {
+        let duration = |d: &str| parse_duration(d.to_string());
+
+        assert_eq!(duration("90m"), Some(Duration::minutes(90)));
+        assert_eq!(duration("1h30m"), Some(Duration::minutes(90)));
+        assert_eq!(duration("2d"), Some(Duration::days(2)));
+        assert_eq!(duration("3 hours"), Some(Duration::hours(3)));
+        assert_eq!(duration("1 hour 30 mins"), Some(Duration::minutes(90)));
+        assert_eq!(duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(duration("P1DT12H"), Some(Duration::hours(36)));
+        assert_eq!(duration("PT"), None);
+        assert_eq!(duration("2"), None);
+        assert_eq!(duration("3 fortnights"), None);
+        assert_eq!(duration("99999999999 hours"), None);
+        assert_eq!(duration(&"2147483647w ".repeat(8)), None);
+
+        assert_eq!(
+            parse_adjustment("- 45m".to_string()),
+            Some(Duration::minutes(-45))
+        );
+        assert_eq!(
+            parse_relative_time("in 6 hours".to_string()),
+            Some(Duration::hours(6))
+        );
     }
 }
@@ -0,0 +1,51 @@
+use crate::{format_time, Config};
+use chrono::Utc;
+use colored::*;
+
+// the people and teams in the config file, with the time where
+// each person is right now
+pub fn list_people(config: &Config) {
+    println!("\n");
+    if config.people.is_empty() && config.teams.is_empty() {
+        println!(
+            "There are no people or teams in the config file, add them as:\n\n\t[people]\n\talice = \"America/New_York\"\n\n\t[teams]\n\tnew-york-team = [\"alice\"]"
+        );
+    }
+
+    let mut people: Vec<(&String, &String)> = config.people.iter().collect();
+    people.sort();
+
+    for (name, zone) in people {
+        let now = config
+            .lookup_timezone(&format!("@{name}"))
+            .map(|timezone| format_time(Utc::now().with_timezone(&timezone), &config.clock))
+            .unwrap_or_default();
+
+        println!(
+            "{:<20}{:<28}{}",
+            format!("@{name}").bold(),
+            zone,
+            now.cyan()
+        );
+    }
+
+    let mut teams: Vec<(&String, &Vec<String>)> = config.teams.iter().collect();
+    teams.sort();
+
+    if !teams.is_empty() {
+        println!();
+    }
+
+    for (name, members) in teams {
+        println!(
+            "{:<20}{}",
+            format!("@{name}").bold(),
+            members
+                .iter()
+                .map(|member| format!("@{member}"))
+                .collect::<Vec<String>>()
+                .join(" ")
+        );
+    }
+    println!("\n");
+}
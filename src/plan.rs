@@ -0,0 +1,253 @@
+use crate::grid::day_bounds;
+use crate::{
+    format_time, parse_date, parse_hours, take_date_option, take_option, today, Settings,
+    WorkingHours,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use colored::*;
+
+// meetings are planned in slots of this length
+const SLOT: Duration = Duration::minutes(30);
+
+// at most this many candidate times are printed
+const CANDIDATES: usize = 10;
+
+// a stretch of time during which the same participants are all
+// within their working hours
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub available: Vec<bool>, // by participant
+}
+
+impl Candidate {
+    pub fn count(&self) -> usize {
+        self.available
+            .iter()
+            .filter(|&&available| available)
+            .count()
+    }
+}
+
+// the stretches of `date` in the first timezone, ranked by how
+// many of the participants are within their working hours
+pub fn find_candidates(participants: &[(Tz, WorkingHours)], date: NaiveDate) -> Vec<Candidate> {
+    let organiser = participants[0].0;
+
+    let Some((day_start, day_end)) = day_bounds(organiser, date) else {
+        return vec![];
+    };
+
+    let mut candidates: Vec<Candidate> = vec![];
+    let mut slot = day_start;
+
+    while slot < day_end {
+        let available: Vec<bool> = participants
+            .iter()
+            .map(|(timezone, hours)| {
+                hours.covers(
+                    slot.with_timezone(timezone).naive_local(),
+                    (slot + SLOT).with_timezone(timezone).naive_local(),
+                )
+            })
+            .collect();
+
+        // consecutive slots with the same participants are one candidate
+        match candidates.last_mut() {
+            Some(last) if last.available == available => last.end = slot + SLOT,
+            _ => candidates.push(Candidate {
+                start: slot,
+                end: slot + SLOT,
+                available,
+            }),
+        }
+
+        slot += SLOT;
+    }
+
+    candidates.retain(|candidate| candidate.count() > 0);
+    candidates.sort_by(|a, b| b.count().cmp(&a.count()).then(a.start.cmp(&b.start)));
+    candidates
+}
+
+/*
+ * pt et bst ist                     - today, in each timezone's working hours
+ * pt et bst ist --hours 10-18       - with the same working hours for everyone
+ * pt et bst ist --date next tue     - on another day
+ *
+ * the date is that of the first timezone
+ */
+pub fn plan(mut args: Vec<String>, settings: &Settings) {
+    let maybe_hours = take_option(&mut args, "--hours");
+    let maybe_date = take_date_option(&mut args, "--date", settings);
+
+    if args.len() < 2 {
+        println!(
+            "\n[{}] expected at least two timezones to plan across.\n\n\tUsage: tz plan timezone timezone... [--hours 9-17] [--date date]\n\tExample: tz plan pt et bst ist --hours 9-17 --date next tue\n",
+            "ERROR".red()
+        );
+        return;
+    }
+
+    let maybe_given_hours = match maybe_hours {
+        Some(given) => match parse_hours(given.clone()) {
+            Some(hours) => Some(hours),
+            None => {
+                println!(
+                    "\n[{}] cannot parse working hours \"{given}\", expected eg. 9-17 or 9am-5pm\n",
+                    "ERROR".red()
+                );
+                return;
+            }
+        },
+        None => None,
+    };
+
+    let date = match parse_date(maybe_date.clone(), settings.locale, settings.order, today()) {
+        Some(date) => date,
+        None => {
+            println!(
+                "\n[{}] could not parse date {:?}\n",
+                "ERROR".red(),
+                maybe_date.unwrap_or_default()
+            );
+            return;
+        }
+    };
+
+    let mut participants = vec![];
+
+    for name in &args {
+        let mut hours = settings.config.working_hours(name, date);
+
+        if let Some((start, end)) = maybe_given_hours {
+            hours.start = start;
+            hours.end = end;
+        }
+
+        match settings.config.lookup_timezone(name) {
+            Some(timezone) => participants.push((timezone, hours)),
+            None => {
+                println!("\n[{}] cannot parse timezone {name}\n", "ERROR".red());
+                return;
+            }
+        }
+    }
+
+    let candidates = find_candidates(&participants, date);
+
+    println!("\n");
+    println!(
+        "Meeting times on {} ({}):\n",
+        date.format("%a %-d %b %Y"),
+        args[0]
+    );
+
+    if candidates.is_empty() {
+        println!("\tno one is in working hours on this day\n");
+        return;
+    }
+
+    let width = 24;
+
+    print!("\t{:<13}", "UTC");
+    for name in &args {
+        print!("{name:<width$}");
+    }
+    println!();
+
+    print!("\t{:<13}", "");
+    for (_, hours) in &participants {
+        print!("{}", format!("{:<width$}", hours.to_string()).dimmed());
+    }
+    println!();
+
+    for candidate in candidates.iter().take(CANDIDATES) {
+        print!(
+            "\t{:<13}",
+            format!(
+                "{}-{}",
+                candidate.start.format("%H:%M"),
+                candidate.end.format("%H:%M")
+            )
+        );
+
+        for (i, (timezone, _)) in participants.iter().enumerate() {
+            let local = format!(
+                "{:<width$}",
+                format!(
+                    "{}-{}",
+                    format_time(
+                        candidate.start.with_timezone(timezone),
+                        &settings.config.clock
+                    ),
+                    format_time(
+                        candidate.end.with_timezone(timezone),
+                        &settings.config.clock
+                    )
+                )
+            );
+
+            if candidate.available[i] {
+                print!("{}", local.green());
+            } else {
+                print!("{}", local.dimmed());
+            }
+        }
+
+        println!("{}/{}", candidate.count(), participants.len());
+    }
+    println!("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{date, utc, zone};
+    use chrono::NaiveTime;
+
+    #[test]
+    fn overlap_ranks_first() {
+        let et = zone("America/New_York");
+        let bst = zone("Europe/London");
+        let participants = [
+            (et, WorkingHours::default()),
+            (bst, WorkingHours::default()),
+        ];
+
+        let candidates = find_candidates(&participants, date(2025, 6, 4));
+
+        // 9am-12pm in New York is 2pm-5pm in London
+        assert_eq!(
+            candidates[0],
+            Candidate {
+                start: utc(2025, 6, 4, 13),
+                end: utc(2025, 6, 4, 16),
+                available: vec![true, true],
+            }
+        );
+        assert_eq!(candidates[0].count(), 2);
+        assert!(candidates[1..]
+            .iter()
+            .all(|candidate| candidate.count() == 1));
+    }
+
+    #[test]
+    fn days_without_a_midnight_are_planned() {
+        let santiago = zone("America/Santiago");
+        let every_day = WorkingHours {
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            weekend: vec![],
+        };
+        let participants = [(santiago, every_day.clone()), (santiago, every_day)];
+
+        // the clocks go from 00:00 to 01:00 on this day
+        let candidates = find_candidates(&participants, date(2025, 9, 7));
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].count(), 2);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{print_help, run, subcommands, Settings, TZ_MAP};
+use colored::*;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::path::PathBuf;
+
+// completes the word under the cursor from the timezone names,
+// people, teams, and subcommands
+struct Completions {
+    words: Vec<String>,
+}
+
+impl Completer for Completions {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos].rfind(' ').map(|i| i + 1).unwrap_or(0);
+        let word = line[start..pos].to_lowercase();
+
+        let matches = self
+            .words
+            .iter()
+            .filter(|candidate| candidate.to_lowercase().starts_with(&word))
+            .cloned()
+            .collect();
+
+        Ok((start, matches))
+    }
+}
+
+impl Hinter for Completions {
+    type Hint = String;
+}
+
+impl Highlighter for Completions {}
+
+impl Validator for Completions {}
+
+impl Helper for Completions {}
+
+// $XDG_STATE_HOME/timezone/history or ~/.local/state/timezone/history
+fn history_path() -> Option<PathBuf> {
+    let state_home = match std::env::var_os("XDG_STATE_HOME").filter(|path| !path.is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".local/state"),
+    };
+
+    Some(state_home.join("timezone/history"))
+}
+
+// set from et, set to bst ist, or set on its own to show them
+fn set(args: &[&str], settings: &mut Settings) {
+    match args {
+        ["from"] => settings.origin = None,
+        ["from", origin] => {
+            if settings.config.lookup_timezone(origin).is_none() {
+                return println!("\n[{}] cannot parse origin {origin}\n", "ERROR".red());
+            }
+            settings.origin = Some(origin.to_string());
+        }
+        ["to", destinations @ ..] => {
+            let destinations = settings
+                .config
+                .expand_teams(destinations.iter().map(|d| d.to_string()).collect());
+
+            if let Some(unknown) = destinations
+                .iter()
+                .find(|destination| settings.config.lookup_timezone(destination).is_none())
+            {
+                return println!("\n[{}] cannot parse destination {unknown}\n", "ERROR".red());
+            }
+            settings.config.destinations = destinations;
+        }
+        [] => {}
+        _ => {
+            return println!(
+                "\n[{}] expected set from <timezone> or set to <timezone>...\n",
+                "ERROR".red()
+            )
+        }
+    }
+
+    println!(
+        "from {}, to {}",
+        settings
+            .origin
+            .as_deref()
+            .unwrap_or("(given each time)")
+            .bold(),
+        match settings.config.destinations.is_empty() {
+            true => "local time".to_string(),
+            false => settings.config.destinations.join(" "),
+        }
+        .bold()
+    );
+}
+
+// reads conversions one line at a time, in the same form as the
+// command line, until exit or ctrl-d
+pub fn repl(mut settings: Settings) {
+    let mut words: Vec<String> = TZ_MAP.keys().map(|key| key.to_string()).collect();
+    words.extend(settings.config.aliases().map(|name| name.to_string()));
+    words.extend(settings.config.people.keys().map(|name| format!("@{name}")));
+    words.extend(settings.config.teams.keys().map(|name| format!("@{name}")));
+    words.extend(subcommands().into_iter().map(String::from));
+    words.extend(["set", "from", "to", "help", "exit"].map(String::from));
+    words.sort();
+    words.dedup();
+
+    let mut editor: Editor<Completions, DefaultHistory> = match Editor::new() {
+        Ok(editor) => editor,
+        Err(error) => {
+            return println!(
+                "\n[{}] cannot start interactive mode: {error}\n",
+                "ERROR".red()
+            )
+        }
+    };
+    editor.set_helper(Some(Completions { words }));
+
+    let history = history_path();
+    if let Some(path) = &history {
+        let _ = editor.load_history(path);
+    }
+
+    println!("Enter a conversion such as 1pm et bst, set from et, set to bst ist, help, or exit.");
+
+    loop {
+        let line = match editor.readline("tz> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(error) => {
+                println!("\n[{}] {error}\n", "ERROR".red());
+                break;
+            }
+        };
+
+        let args: Vec<&str> = line.split_whitespace().collect();
+        if args.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line.as_str());
+
+        match args[0] {
+            "exit" | "quit" => break,
+            "help" => {
+                print_help();
+                println!("Interactively, set from <timezone> makes it the origin of every conversion after it, and set to <timezone>... makes those the destinations when none are given.\n");
+            }
+            "set" => set(&args[1..], &mut settings),
+            _ => run(args.iter().map(|arg| arg.to_string()).collect(), &settings),
+        }
+    }
+
+    if let Some(path) = &history {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = editor.save_history(path);
+    }
+}
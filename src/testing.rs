@@ -0,0 +1,40 @@
+// fixtures shared by the tests of every module
+use crate::{Config, DateOrder, Settings, ENGLISH};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+
+pub fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+// the start of an hour in UTC
+pub fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+}
+
+// an IANA timezone, eg. Europe/London
+pub fn zone(name: &str) -> Tz {
+    name.parse().unwrap()
+}
+
+// a config file with these contents
+pub fn config(contents: &str) -> Config {
+    Config::from_toml(contents)
+}
+
+// English, day first, without warnings, and with `config`
+pub fn settings_with(config: Config) -> Settings {
+    Settings {
+        locale: &ENGLISH,
+        order: DateOrder::DayFirst,
+        config,
+        origin: None,
+        quiet: false,
+        warnings: false,
+    }
+}
+
+// as above, without a config file
+pub fn settings() -> Settings {
+    settings_with(Config::default())
+}
@@ -0,0 +1,233 @@
+use crate::{take_option, Settings};
+use chrono::{DateTime, Duration, FixedOffset, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetName, Tz};
+use colored::*;
+
+// no timezone changes its offset twice within this long, so
+// stepping by it cannot miss a transition
+const STEP: Duration = Duration::hours(12);
+
+// the UTC offset of `timezone` at `instant`
+pub fn utc_offset(timezone: Tz, instant: DateTime<Utc>) -> FixedOffset {
+    timezone
+        .offset_from_utc_datetime(&instant.naive_utc())
+        .fix()
+}
+
+// the abbreviation in use in `timezone` at `instant`, eg. BST
+pub fn abbreviation(timezone: Tz, instant: DateTime<Utc>) -> String {
+    timezone
+        .offset_from_utc_datetime(&instant.naive_utc())
+        .abbreviation()
+        .to_string()
+}
+
+// a change in the UTC offset of a timezone, such as the clocks
+// going forward for daylight saving
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transition {
+    pub instant: DateTime<Utc>, // the first second of the new offset
+    pub before: FixedOffset,
+    pub after: FixedOffset,
+    pub before_abbreviation: String,
+    pub after_abbreviation: String,
+}
+
+// the changes of UTC offset in `timezone` between `from` and `to`
+pub fn transitions(timezone: Tz, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Transition> {
+    let mut found = vec![];
+    let mut before = from;
+
+    while before < to {
+        let after = (before + STEP).min(to);
+
+        if utc_offset(timezone, before) != utc_offset(timezone, after) {
+            let instant = bisect(timezone, before, after);
+            let last_before = instant - Duration::seconds(1);
+
+            found.push(Transition {
+                instant,
+                before: utc_offset(timezone, last_before),
+                after: utc_offset(timezone, instant),
+                before_abbreviation: abbreviation(timezone, last_before),
+                after_abbreviation: abbreviation(timezone, instant),
+            });
+        }
+
+        before = after;
+    }
+
+    found
+}
+
+// narrows down the change of offset between `before` and `after`
+// to the second, as offsets only change on whole seconds
+fn bisect(timezone: Tz, before: DateTime<Utc>, after: DateTime<Utc>) -> DateTime<Utc> {
+    let offset = utc_offset(timezone, before);
+    let at = |seconds| DateTime::from_timestamp(seconds, 0).unwrap_or(after);
+    let (mut before, mut after) = (before.timestamp(), after.timestamp());
+
+    while after - before > 1 {
+        let middle = before + (after - before) / 2;
+
+        if utc_offset(timezone, at(middle)) == offset {
+            before = middle;
+        } else {
+            after = middle;
+        }
+    }
+
+    at(after)
+}
+
+// +01:00 -> +1:00, -03:30 -> -3:30
+pub fn format_offset(offset: FixedOffset) -> String {
+    let seconds = offset.local_minus_utc();
+    let sign = if seconds < 0 { "-" } else { "+" };
+
+    format!(
+        "{sign}{}:{:0>2}",
+        seconds.abs() / 3600,
+        seconds.abs() % 3600 / 60
+    )
+}
+
+/*
+ * Europe/London             - the transitions in the past and coming year
+ * Europe/London --year 2025 - the transitions in 2025
+ */
+pub fn list_transitions(mut args: Vec<String>, settings: &Settings) {
+    let maybe_year = take_option(&mut args, "--year");
+
+    if args.len() != 1 {
+        println!(
+            "\n[{}] expected one timezone.\n\n\tUsage: tz transitions timezone [--year year]\n\tExample: tz transitions Europe/London --year 2025\n",
+            "ERROR".red()
+        );
+        return;
+    }
+
+    let maybe_timezone = settings.config.lookup_timezone(&args[0]);
+
+    if maybe_timezone.is_none() {
+        println!("\n[{}] cannot parse timezone {}\n", "ERROR".red(), args[0]);
+        return;
+    }
+
+    let timezone = maybe_timezone.unwrap();
+    let now = Utc::now();
+
+    let (from, to) = match maybe_year {
+        Some(year) => {
+            let maybe_year_n = year.parse::<i32>().ok().and_then(|y| {
+                Some((
+                    Utc.with_ymd_and_hms(y, 1, 1, 0, 0, 0).single()?,
+                    Utc.with_ymd_and_hms(y + 1, 1, 1, 0, 0, 0).single()?,
+                ))
+            });
+
+            match maybe_year_n {
+                Some(range) => range,
+                None => {
+                    println!("\n[{}] cannot parse year {year}\n", "ERROR".red());
+                    return;
+                }
+            }
+        }
+        None => (now - Duration::days(365), now + Duration::days(365)),
+    };
+
+    let found = transitions(timezone, from, to);
+
+    println!("\n");
+    if found.is_empty() {
+        println!(
+            "{} does not change its UTC offset from {} to {}, it is always {} ({})",
+            args[0],
+            from.format("%-d %b %Y"),
+            to.format("%-d %b %Y"),
+            abbreviation(timezone, from),
+            format_offset(utc_offset(timezone, from))
+        );
+    }
+
+    for transition in found {
+        let local_before = transition.instant.with_timezone(&transition.before);
+        let local_after = transition.instant.with_timezone(&transition.after);
+
+        let line = format!(
+            "{}  {} {} ({}) -> {} {} ({})",
+            local_after.format("%a %-d %b %Y"),
+            local_before.format("%-I:%M%P"),
+            transition.before_abbreviation,
+            format_offset(transition.before),
+            local_after.format("%-I:%M%P"),
+            transition.after_abbreviation,
+            format_offset(transition.after),
+        );
+
+        // past transitions are dimmed
+        if transition.instant < now {
+            println!("{}", line.dimmed());
+        } else {
+            println!("{line}");
+        }
+    }
+    println!("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{utc, zone};
+
+    fn offset(hours: i32) -> FixedOffset {
+        FixedOffset::east_opt(hours * 3600).unwrap()
+    }
+
+    #[test]
+    fn london_in_2025() {
+        let london = zone("Europe/London");
+        let found = transitions(london, utc(2025, 1, 1, 0), utc(2026, 1, 1, 0));
+
+        assert_eq!(
+            found,
+            vec![
+                Transition {
+                    instant: utc(2025, 3, 30, 1),
+                    before: offset(0),
+                    after: offset(1),
+                    before_abbreviation: "GMT".to_string(),
+                    after_abbreviation: "BST".to_string(),
+                },
+                Transition {
+                    instant: utc(2025, 10, 26, 1),
+                    before: offset(1),
+                    after: offset(0),
+                    before_abbreviation: "BST".to_string(),
+                    after_abbreviation: "GMT".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn no_transitions_without_daylight_saving() {
+        let kolkata = zone("Asia/Kolkata");
+
+        assert_eq!(
+            transitions(kolkata, utc(2025, 1, 1, 0), utc(2026, 1, 1, 0)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn bisect_finds_the_second() {
+        let london = zone("Europe/London");
+
+        assert_eq!(
+            bisect(london, utc(2025, 3, 29, 20), utc(2025, 3, 30, 8)),
+            utc(2025, 3, 30, 1)
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use crate::grid::{hours_of_day, NIGHT_END, NIGHT_START};
+use crate::{format_time, local_timezone, Settings, TZ_MAP};
+use chrono::{DateTime, Duration, NaiveDate, Timelike, Utc};
+use chrono_tz::Tz;
+use colored::*;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+// how far the cursor moves along the timeline at a time
+const STEP: Duration = Duration::minutes(30);
+// the most matches shown when adding a zone
+const MATCHES: usize = 10;
+
+// the zone being searched for to add
+struct Search {
+    query: String,
+    selected: usize,
+}
+
+struct App<'a> {
+    settings: &'a Settings,
+    zones: Vec<(String, Tz)>,
+    date: NaiveDate, // in the first zone
+    cursor: i64,     // steps since the day began in the first zone
+    selected: usize, // the zone row
+    search: Option<Search>,
+    message: Option<String>,
+}
+
+// 0 for a prefix, 1 for a substring, 2 for the letters of the query
+// in order, None when it does not match at all
+fn fuzzy_score(candidate: &str, query: &str) -> Option<usize> {
+    let (candidate, query) = (candidate.to_lowercase(), query.to_lowercase());
+
+    if candidate.starts_with(&query) {
+        return Some(0);
+    }
+    if candidate.contains(&query) {
+        return Some(1);
+    }
+
+    let mut letters = candidate.chars();
+    query.chars().all(|q| letters.any(|c| c == q)).then_some(2)
+}
+
+impl App<'_> {
+    fn first_zone(&self) -> Tz {
+        self.zones[0].1
+    }
+
+    // the hours of the day being shown, in the first zone
+    fn hours(&self) -> Vec<DateTime<Utc>> {
+        hours_of_day(self.first_zone(), self.date)
+    }
+
+    fn cursor_instant(&self) -> DateTime<Utc> {
+        let start = self.hours().first().copied().unwrap_or_else(Utc::now);
+        start + STEP * self.cursor as i32
+    }
+
+    // today in the first zone, with the cursor on the current time
+    fn now(&mut self) {
+        let now = Utc::now();
+        self.date = now.with_timezone(&self.first_zone()).date_naive();
+
+        let start = self.hours().first().copied().unwrap_or(now);
+        self.cursor = (now - start).num_minutes() / STEP.num_minutes();
+    }
+
+    fn matches(&self, query: &str) -> Vec<String> {
+        let mut names: Vec<String> = TZ_MAP.keys().map(|key| key.to_string()).collect();
+        names.extend(self.settings.config.aliases().cloned());
+        names.extend(
+            self.settings
+                .config
+                .people
+                .keys()
+                .map(|name| format!("@{name}")),
+        );
+
+        let mut scored: Vec<(usize, String)> = names
+            .into_iter()
+            .filter_map(|name| Some((fuzzy_score(&name, query)?, name)))
+            .collect();
+        scored.sort_by(|(a, a_name), (b, b_name)| {
+            (a, a_name.len(), a_name).cmp(&(b, b_name.len(), b_name))
+        });
+        scored.dedup_by(|(_, a), (_, b)| a == b);
+
+        scored
+            .into_iter()
+            .take(MATCHES)
+            .map(|(_, name)| name)
+            .collect()
+    }
+
+    fn add_zone(&mut self, name: String) {
+        match self.settings.config.lookup_timezone(&name) {
+            Some(timezone) => {
+                self.zones.push((name, timezone));
+                self.selected = self.zones.len() - 1;
+            }
+            None => self.message = Some(format!("cannot parse timezone {name}")),
+        }
+    }
+
+    fn remove_zone(&mut self) {
+        if self.zones.len() == 1 {
+            self.message = Some("there has to be at least one zone".to_string());
+            return;
+        }
+
+        // keep the cursor on the same instant when the first zone goes
+        let instant = self.cursor_instant();
+        self.zones.remove(self.selected);
+        self.selected = self.selected.min(self.zones.len() - 1);
+
+        let local = instant.with_timezone(&self.first_zone());
+        self.date = local.date_naive();
+        let start = self.hours().first().copied().unwrap_or(instant);
+        self.cursor = (instant - start).num_minutes() / STEP.num_minutes();
+    }
+
+    // true to quit
+    fn handle_key(&mut self, key: KeyCode) -> bool {
+        self.message = None;
+
+        if let Some(search) = &mut self.search {
+            match key {
+                KeyCode::Esc => self.search = None,
+                KeyCode::Enter => {
+                    let query = search.query.clone();
+                    let selected = search.selected;
+                    self.search = None;
+
+                    if let Some(name) = self.matches(&query).into_iter().nth(selected) {
+                        self.add_zone(name);
+                    }
+                }
+                KeyCode::Up => search.selected = search.selected.saturating_sub(1),
+                KeyCode::Down => search.selected = (search.selected + 1).min(MATCHES - 1),
+                KeyCode::Backspace => {
+                    search.query.pop();
+                    search.selected = 0;
+                }
+                KeyCode::Char(c) => {
+                    search.query.push(c);
+                    search.selected = 0;
+                }
+                _ => {}
+            }
+            return false;
+        }
+
+        let steps = self.hours().len() as i64 * (60 / STEP.num_minutes());
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Left | KeyCode::Char('h') => self.cursor = (self.cursor - 1).max(0),
+            KeyCode::Right | KeyCode::Char('l') => self.cursor = (self.cursor + 1).min(steps - 1),
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.zones.len() - 1)
+            }
+            KeyCode::Char('[') => self.date = self.date.pred_opt().unwrap_or(self.date),
+            KeyCode::Char(']') => self.date = self.date.succ_opt().unwrap_or(self.date),
+            KeyCode::Char('t') => self.now(),
+            KeyCode::Char('a') => {
+                self.search = Some(Search {
+                    query: String::new(),
+                    selected: 0,
+                })
+            }
+            KeyCode::Char('d') | KeyCode::Delete => self.remove_zone(),
+            _ => {}
+        }
+
+        // a shorter day, such as when the clocks go forward
+        let steps = self.hours().len() as i64 * (60 / STEP.num_minutes());
+        self.cursor = self.cursor.min(steps - 1);
+
+        false
+    }
+
+    fn cell_style(&self, name: &str, local: DateTime<Tz>, column: usize) -> Style {
+        let hours = self.settings.config.working_hours(name, local.date_naive());
+        let cursor_column = self.cursor as usize * STEP.num_minutes() as usize / 60;
+
+        let style = if hours.covers(
+            local.naive_local(),
+            (local + Duration::hours(1)).naive_local(),
+        ) {
+            Style::default().fg(Color::Green)
+        } else if !(NIGHT_END..NIGHT_START).contains(&local.hour()) {
+            Style::default().fg(Color::Blue).add_modifier(Modifier::DIM)
+        } else {
+            Style::default()
+        };
+
+        if column == cursor_column {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style
+        }
+    }
+
+    fn zone_line(&self, row: usize, hours: &[DateTime<Utc>], instant: DateTime<Utc>) -> Line<'_> {
+        let (name, timezone) = &self.zones[row];
+        let local = instant.with_timezone(timezone);
+        let marker = if row == self.selected { "▶ " } else { "  " };
+
+        let mut spans = vec![
+            Span::styled(
+                format!("{marker}{name:<18}"),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                "{:<10}{:<12}",
+                format_time(local, &self.settings.config.clock),
+                local.format("%a %-d %b").to_string()
+            )),
+        ];
+
+        for (column, hour) in hours.iter().enumerate() {
+            let local_hour = hour.with_timezone(timezone);
+            spans.push(Span::styled(
+                local_hour.format("%H").to_string(),
+                self.cell_style(name, local_hour, column),
+            ));
+            spans.push(Span::raw(" "));
+        }
+
+        Line::from(spans)
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [title, table, help] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let hours = self.hours();
+        let instant = self.cursor_instant();
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                "{} in {}",
+                self.date.format("%A %-d %B %Y"),
+                self.zones[0].0
+            ))
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+            title,
+        );
+
+        let lines: Vec<Line> = (0..self.zones.len())
+            .map(|row| self.zone_line(row, &hours, instant))
+            .collect();
+        frame.render_widget(Paragraph::new(lines), table);
+
+        let help_text = match &self.message {
+            Some(message) => message.clone(),
+            None => "←/→ move  ↑/↓ zone  [/] day  t now  a add  d remove  q quit".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(help_text).style(Style::default().add_modifier(Modifier::DIM)),
+            help,
+        );
+
+        if let Some(search) = &self.search {
+            self.draw_search(frame, search);
+        }
+    }
+
+    fn draw_search(&self, frame: &mut Frame, search: &Search) {
+        let area = frame.area();
+        let width = 50.min(area.width);
+        let height = (MATCHES as u16 + 3).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+
+        let mut lines = vec![Line::from(format!("> {}", search.query))];
+        for (i, name) in self.matches(&search.query).into_iter().enumerate() {
+            let style = if i == search.selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::styled(name, style));
+        }
+
+        frame.render_widget(Clear, popup);
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Add a zone")),
+            popup,
+        );
+    }
+}
+
+fn run(terminal: &mut DefaultTerminal, app: &mut App) -> std::io::Result<()> {
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && app.handle_key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/*
+ * pt et bst ist   - these zones, otherwise the configured
+ *                   destinations, or else the local timezone
+ */
+pub fn tui(args: Vec<String>, settings: &Settings) {
+    let names = if !args.is_empty() {
+        args
+    } else if !settings.config.destinations.is_empty() {
+        settings.config.destinations.clone()
+    } else {
+        vec![settings.config.local.clone().unwrap_or_else(local_timezone)]
+    };
+
+    let mut zones = vec![];
+    for name in names {
+        match settings.config.lookup_timezone(&name) {
+            Some(timezone) => zones.push((name, timezone)),
+            None => return println!("\n[{}] cannot parse timezone {name}\n", "ERROR".red()),
+        }
+    }
+
+    let mut app = App {
+        settings,
+        zones,
+        date: Utc::now().date_naive(),
+        cursor: 0,
+        selected: 0,
+        search: None,
+        message: None,
+    };
+    app.now();
+
+    let mut terminal = match ratatui::try_init() {
+        Ok(terminal) => terminal,
+        Err(error) => return println!("\n[{}] cannot start the tui: {error}\n", "ERROR".red()),
+    };
+    let result = run(&mut terminal, &mut app);
+    ratatui::restore();
+
+    if let Err(error) = result {
+        println!("\n[{}] {error}\n", "ERROR".red());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{config, date, settings, settings_with, zone};
+
+    fn app<'a>(settings: &'a Settings, zones: &[&str]) -> App<'a> {
+        App {
+            settings,
+            zones: zones
+                .iter()
+                .map(|name| (name.to_string(), zone(name)))
+                .collect(),
+            date: date(2025, 3, 30),
+            cursor: 0,
+            selected: 0,
+            search: None,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn fuzzy_scores() {
+        assert_eq!(fuzzy_score("Europe/London", "eur"), Some(0));
+        assert_eq!(fuzzy_score("Europe/London", "LONDON"), Some(1));
+        assert_eq!(fuzzy_score("Europe/London", "eln"), Some(2));
+        assert_eq!(fuzzy_score("Europe/London", "nle"), None);
+    }
+
+    #[test]
+    fn matches_come_best_first() {
+        let settings = settings_with(config(
+            r#"
+            [people]
+            bob = "Europe/London"
+            "#,
+        ));
+        let app = app(&settings, &["Europe/London"]);
+
+        assert_eq!(app.matches("@bo"), ["@bob"]);
+        assert_eq!(app.matches("").len(), MATCHES);
+
+        let scores: Vec<_> = app
+            .matches("lo")
+            .iter()
+            .map(|name| fuzzy_score(name, "lo").unwrap())
+            .collect();
+        assert!(scores.is_sorted(), "{scores:?}");
+    }
+
+    #[test]
+    fn the_cursor_stays_within_the_day() {
+        let settings = settings();
+        // the clocks go forward in London, so the day has 23 hours
+        let mut app = app(&settings, &["Europe/London", "Asia/Tokyo"]);
+        let steps = 23 * 2;
+
+        app.handle_key(KeyCode::Left);
+        assert_eq!(app.cursor, 0);
+
+        for _ in 0..100 {
+            app.handle_key(KeyCode::Right);
+        }
+        assert_eq!(app.cursor, steps - 1);
+
+        app.handle_key(KeyCode::Char('h'));
+        assert_eq!(app.cursor, steps - 2);
+
+        // the next day is a full one, and the one after is kept to it
+        app.handle_key(KeyCode::Char(']'));
+        assert_eq!(app.date, date(2025, 3, 31));
+        assert_eq!(app.cursor, steps - 2);
+        app.cursor = 24 * 2 - 1;
+        app.handle_key(KeyCode::Char('['));
+        assert_eq!(app.cursor, steps - 1);
+
+        app.handle_key(KeyCode::Down);
+        app.handle_key(KeyCode::Down);
+        assert_eq!(app.selected, 1);
+        app.handle_key(KeyCode::Up);
+        assert_eq!(app.selected, 0);
+
+        assert!(!app.handle_key(KeyCode::Char('a')));
+        assert!(app.search.is_some());
+        assert!(!app.handle_key(KeyCode::Char('q')));
+        assert!(!app.handle_key(KeyCode::Esc));
+        assert!(app.handle_key(KeyCode::Char('q')));
+    }
+}
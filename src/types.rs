@@ -1,3 +1,6 @@
+use chrono::{Datelike, NaiveDateTime, NaiveTime, Weekday};
+use std::fmt;
+
 pub enum Time {
     Hours(u32),
     Minutes(u32),
@@ -27,3 +30,132 @@ pub enum TimeFormat {
     MilitaryColon, // 13:00
     Military,      // 1400
 }
+
+// how times are written out
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Clock {
+    #[default]
+    TwelveHour, // 1:00pm
+    TwentyFourHour, // 13:00
+    Custom(String), // a strftime format, eg. %H.%M
+}
+
+impl Clock {
+    pub fn parse(clock: &str) -> Option<Clock> {
+        match clock.to_lowercase().as_str() {
+            "12" | "12h" => Some(Clock::TwelveHour),
+            "24" | "24h" => Some(Clock::TwentyFourHour),
+            _ => None,
+        }
+    }
+}
+
+// which of the day and month comes first in an ambiguous
+// numeric date such as 02/03
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateOrder {
+    DayFirst,   // 02/03 is the 2nd of March
+    MonthFirst, // 02/03 is the 3rd of February
+}
+
+impl DateOrder {
+    pub fn parse(order: &str) -> Option<DateOrder> {
+        match order.to_lowercase().as_str() {
+            "dmy" | "day" | "day-first" => Some(DateOrder::DayFirst),
+            "mdy" | "month" | "month-first" => Some(DateOrder::MonthFirst),
+            _ => None,
+        }
+    }
+
+    // month first for the locales that write dates that way,
+    // otherwise day first
+    pub fn from_env() -> DateOrder {
+        let locale = crate::env_locale();
+
+        if ["en_US", "en_PH"]
+            .iter()
+            .any(|prefix| locale.starts_with(prefix))
+        {
+            DateOrder::MonthFirst
+        } else {
+            DateOrder::DayFirst
+        }
+    }
+}
+
+// the hours of the day that count as working hours, and the
+// days of the week that do not count at all
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkingHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub weekend: Vec<Weekday>,
+}
+
+impl Default for WorkingHours {
+    // 9am-5pm, monday to friday
+    fn default() -> WorkingHours {
+        WorkingHours {
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            weekend: vec![Weekday::Sat, Weekday::Sun],
+        }
+    }
+}
+
+impl WorkingHours {
+    // whether the whole of `start` to `end`, in local time, is
+    // within working hours on a working day
+    pub fn covers(&self, start: NaiveDateTime, end: NaiveDateTime) -> bool {
+        start.date() == end.date()
+            && !self.weekend.contains(&start.weekday())
+            && start.time() >= self.start
+            && end.time() <= self.end
+    }
+
+    // within working hours, before working hours, ...
+    pub fn describe(&self, start: NaiveDateTime, end: NaiveDateTime) -> &'static str {
+        if self.weekend.contains(&start.weekday()) {
+            "on the weekend"
+        } else if self.covers(start, end) {
+            "within working hours"
+        } else if start.time() < self.start
+            && end.time() <= self.start
+            && start.date() == end.date()
+        {
+            "before working hours"
+        } else if start.time() >= self.end {
+            "after working hours"
+        } else {
+            "partly within working hours"
+        }
+    }
+}
+
+// 9:00am-5:00pm mon-fri
+impl fmt::Display for WorkingHours {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let days: Vec<Weekday> = (0..7u8)
+            .filter_map(|n| Weekday::try_from(n).ok())
+            .filter(|day| !self.weekend.contains(day))
+            .collect();
+
+        let name = |day: &Weekday| day.to_string().to_lowercase();
+
+        let contiguous = days.windows(2).all(|pair| pair[0].succ() == pair[1]);
+
+        let days = match (days.first(), days.last()) {
+            (Some(first), Some(last)) if contiguous && days.len() > 2 => {
+                format!("{}-{}", name(first), name(last))
+            }
+            _ => days.iter().map(name).collect::<Vec<String>>().join(", "),
+        };
+
+        write!(
+            f,
+            "{}-{} {days}",
+            self.start.format("%-I:%M%P"),
+            self.end.format("%-I:%M%P")
+        )
+    }
+}
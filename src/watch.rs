@@ -0,0 +1,124 @@
+use crate::{convert_instant, local_timezone, output_lines, parse_duration, take_option, Settings};
+use chrono::{Duration, Utc};
+use colored::*;
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+// how often ctrl-c is checked for while waiting
+const POLL: std::time::Duration = std::time::Duration::from_millis(100);
+
+// the current time in every zone, with those on another day than
+// the first zone highlighted and given their date
+fn render(zones: &[String], settings: &Settings) -> Result<Vec<String>, String> {
+    let (given, converted) = convert_instant(
+        "now".to_string(),
+        Utc::now(),
+        zones.to_vec(),
+        vec![],
+        settings,
+    )?;
+    let first_day = converted[0].day;
+
+    Ok(output_lines(&given, &converted)
+        .into_iter()
+        .zip(&converted)
+        .map(|(line, converted)| {
+            let days = (converted.day - first_day).num_days();
+
+            if days == 0 {
+                line
+            } else {
+                format!(
+                    "{} {}",
+                    line.yellow().bold(),
+                    format!("({days:+}, {})", converted.day.format("%a %-d %b")).yellow()
+                )
+            }
+        })
+        .collect())
+}
+
+/*
+ * pt et bst ist                - redraws every second until ctrl-c
+ * pt et bst ist --interval 30s - or every 30 seconds
+ */
+pub fn watch(mut args: Vec<String>, settings: &Settings) {
+    let interval = match take_option(&mut args, "--interval") {
+        Some(interval) => match parse_duration(interval.clone()) {
+            Some(duration) if duration > Duration::zero() => duration,
+            _ => {
+                return println!(
+                    "\n[{}] cannot parse the interval {interval}, expected eg. 1s, 30s, 1m.\n",
+                    "ERROR".red()
+                );
+            }
+        },
+        None => Duration::seconds(1),
+    };
+
+    let running = Arc::new(AtomicBool::new(true));
+    let handler_running = running.clone();
+    if let Err(error) = ctrlc::set_handler(move || handler_running.store(false, Ordering::SeqCst)) {
+        return println!("\n[{}] cannot handle ctrl-c: {error}\n", "ERROR".red());
+    }
+
+    // the local timezone is found once, rather than warned about on
+    // every redraw
+    if args.is_empty() && settings.config.destinations.is_empty() {
+        let local = settings.config.local.clone().unwrap_or_else(|| {
+            let local = local_timezone();
+            if settings.warnings {
+                println!(
+                    "\n[{}] no zones given. Using local timezone = {local}.",
+                    "WARNING".yellow()
+                );
+            }
+            local
+        });
+        args.push(local);
+    }
+
+    // redraw in place on a terminal, otherwise one drawing after another
+    let terminal = std::io::stdout().is_terminal();
+    let mut drawn = 0;
+
+    println!();
+    // hide the cursor while redrawing
+    if terminal {
+        print!("\x1b[?25l");
+    }
+
+    while running.load(Ordering::SeqCst) {
+        let lines = match render(&args, settings) {
+            Ok(lines) => lines,
+            Err(error) => {
+                println!("\n[{}] {error}\n", "ERROR".red());
+                break;
+            }
+        };
+
+        // back to the top of the previous drawing, clearing each line
+        if terminal && drawn > 0 {
+            print!("\x1b[{drawn}A");
+        }
+        for line in &lines {
+            if terminal {
+                print!("\x1b[2K");
+            }
+            println!("{line}");
+        }
+        drawn = lines.len();
+        let _ = std::io::stdout().flush();
+
+        let next = std::time::Instant::now() + interval.to_std().unwrap_or(POLL);
+        while running.load(Ordering::SeqCst) && std::time::Instant::now() < next {
+            std::thread::sleep(POLL.min(next - std::time::Instant::now()));
+        }
+    }
+
+    if terminal {
+        print!("\x1b[?25h");
+    }
+    println!();
+}
@@ -0,0 +1,192 @@
+use crate::{
+    abbreviation, format_offset, format_time, take_option, transitions, utc_offset, Settings,
+    TZ_MAP,
+};
+use chrono::{Duration, Utc};
+use chrono_tz::Tz;
+use colored::*;
+
+// +5:30, -8, +05:00 -> the offset in seconds
+fn parse_offset(offset: &str) -> Option<i32> {
+    let (sign, rest) = match offset.chars().next()? {
+        '+' => (1, &offset[1..]),
+        '-' => (-1, &offset[1..]),
+        _ => (1, offset),
+    };
+    let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+
+    // only digits after the sign, which parse would let a second sign
+    // into, as in ++5 or +-5
+    let digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+    if !digits(hours) || !digits(minutes) {
+        return None;
+    }
+    let (hours, minutes): (i32, i32) = (hours.parse().ok()?, minutes.parse().ok()?);
+
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+// every name that can be given for a timezone, including the
+// aliases in the config file, with the timezone it stands for
+fn names(settings: &Settings) -> Vec<(String, Tz)> {
+    let mut names: Vec<String> = TZ_MAP.keys().map(|key| key.to_string()).collect();
+    names.extend(settings.config.aliases().cloned());
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter_map(|name| Some((name.clone(), settings.config.lookup_timezone(&name)?)))
+        .collect()
+}
+
+/*
+ * kolkata         - the names containing kolkata, in the name or
+ *                   the IANA timezone
+ * --abbr          - only abbreviations such as ist
+ * --iana          - only IANA names such as Asia/Kolkata
+ * --offset +5:30  - only those currently at this UTC offset
+ */
+pub fn zones(mut args: Vec<String>, settings: &Settings) {
+    let abbreviations_only = args.iter().any(|arg| arg == "--abbr");
+    let iana_only = args.iter().any(|arg| arg == "--iana");
+    args.retain(|arg| arg != "--abbr" && arg != "--iana");
+
+    let offset_given = args
+        .iter()
+        .any(|arg| arg == "--offset" || arg.starts_with("--offset="));
+    let offset = match take_option(&mut args, "--offset") {
+        None if offset_given => {
+            return println!(
+                "\n[{}] --offset needs an offset, eg. +5:30 or -8.\n",
+                "ERROR".red()
+            );
+        }
+        Some(offset) => match parse_offset(&offset) {
+            Some(seconds) => Some(seconds),
+            None => {
+                return println!(
+                    "\n[{}] cannot parse offset {offset}, expected eg. +5:30 or -8.\n",
+                    "ERROR".red()
+                );
+            }
+        },
+        None => None,
+    };
+
+    let query = args.join(" ").to_lowercase();
+    let now = Utc::now();
+
+    let found: Vec<(String, Tz)> = names(settings)
+        .into_iter()
+        .filter(|(name, timezone)| {
+            let iana = name == timezone.name();
+
+            (!abbreviations_only || !iana)
+                && (!iana_only || iana)
+                && (name.to_lowercase().contains(&query)
+                    || timezone.name().to_lowercase().contains(&query))
+                && offset
+                    .is_none_or(|seconds| utc_offset(*timezone, now).local_minus_utc() == seconds)
+        })
+        .collect();
+
+    println!("\n");
+    if found.is_empty() {
+        println!("No timezones match {:?}.", args.join(" "));
+    }
+
+    for (name, timezone) in found {
+        println!(
+            "{:<34}{:<34}{:<8}{}",
+            name,
+            timezone.name().dimmed(),
+            format_offset(utc_offset(timezone, now)),
+            abbreviation(timezone, now)
+        );
+    }
+    println!("\n");
+}
+
+// everything about one timezone name
+pub fn zone(args: Vec<String>, settings: &Settings) {
+    if args.len() != 1 {
+        return println!(
+            "\n[{}] expected one timezone.\n\n\tUsage: tz zone timezone\n\tExample: tz zone ist\n",
+            "ERROR".red()
+        );
+    }
+
+    let name = &args[0];
+    let Some(timezone) = settings.config.lookup_timezone(name) else {
+        return println!("\n[{}] cannot parse timezone {name}\n", "ERROR".red());
+    };
+
+    let now = Utc::now();
+    let local = now.with_timezone(&timezone);
+
+    let other_names: Vec<String> = names(settings)
+        .into_iter()
+        .filter(|(other, other_timezone)| *other_timezone == timezone && other != name)
+        .map(|(other, _)| other)
+        .collect();
+
+    let next_change = match transitions(timezone, now, now + Duration::days(366)).first() {
+        Some(transition) => format!(
+            "{} {} ({}) -> {} ({})",
+            transition
+                .instant
+                .with_timezone(&timezone)
+                .format("%a %-d %b %Y"),
+            transition.before_abbreviation,
+            format_offset(transition.before),
+            transition.after_abbreviation,
+            format_offset(transition.after)
+        ),
+        None => "none in the coming year".to_string(),
+    };
+
+    println!("\n");
+    println!("{}", name.bold());
+    println!("  {:<16}{}", "timezone", timezone.name());
+    println!(
+        "  {:<16}{} {} ({}), {}",
+        "now",
+        format_time(local, &settings.config.clock),
+        abbreviation(timezone, now),
+        format_offset(utc_offset(timezone, now)),
+        local.format("%a %-d %b %Y")
+    );
+    println!("  {:<16}{next_change}", "next change");
+    println!(
+        "  {:<16}{}",
+        "working hours",
+        settings.config.working_hours(name, local.date_naive())
+    );
+    if !other_names.is_empty() {
+        println!("  {:<16}{}", "also known as", other_names.join(", "));
+    }
+    println!("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets() {
+        assert_eq!(parse_offset("+5:30"), Some(5 * 3600 + 30 * 60));
+        assert_eq!(parse_offset("-8"), Some(-8 * 3600));
+        assert_eq!(parse_offset("+05:00"), Some(5 * 3600));
+        assert_eq!(parse_offset("0"), Some(0));
+        assert_eq!(parse_offset("++5"), None);
+        assert_eq!(parse_offset("+-5"), None);
+        assert_eq!(parse_offset("+5:-30"), None);
+        assert_eq!(parse_offset("+"), None);
+        assert_eq!(parse_offset("+15"), None);
+    }
+}